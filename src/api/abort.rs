@@ -0,0 +1,75 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+use web_sys::{AbortController, AbortSignal};
+use yew::platform::spawn_local;
+
+thread_local! {
+    static CURRENT_SIGNAL: RefCell<Option<AbortSignal>> = RefCell::new(None);
+}
+
+/// Groups async work that should stop together, usually when a component unmounts.
+/// Futures spawned in the scope are dropped once it is aborted and every request
+/// they have in flight is cancelled through the scope's `AbortSignal`.
+#[derive(Clone)]
+pub struct AbortScope(Rc<AbortController>);
+
+impl PartialEq for AbortScope {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Default for AbortScope {
+    fn default() -> Self {
+        Self(Rc::new(AbortController::new().unwrap()))
+    }
+}
+
+impl AbortScope {
+    pub fn abort(&self) {
+        self.0.abort();
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.0.signal().aborted()
+    }
+
+    pub fn spawn(&self, future: impl Future<Output = ()> + 'static) {
+        if self.is_aborted() {
+            return;
+        }
+        spawn_local(Scoped {
+            signal: self.0.signal(),
+            future: Box::pin(future),
+        });
+    }
+
+    /// Signal of the scope that is polling the current future, if any.
+    pub fn current_signal() -> Option<AbortSignal> {
+        CURRENT_SIGNAL.with(|s| s.borrow().clone())
+    }
+}
+
+struct Scoped {
+    signal: AbortSignal,
+    future: Pin<Box<dyn Future<Output = ()>>>,
+}
+
+impl Future for Scoped {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.signal.aborted() {
+            return Poll::Ready(());
+        }
+        let prev = CURRENT_SIGNAL.with(|s| s.replace(Some(self.signal.clone())));
+        let poll = self.future.as_mut().poll(cx);
+        CURRENT_SIGNAL.with(|s| *s.borrow_mut() = prev);
+        poll
+    }
+}
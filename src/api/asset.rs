@@ -0,0 +1,101 @@
+/// Extensions read as archives, as a file input `accept` list.
+macro_rules! archives {
+    () => {
+        ".zip,.7z,.rar,.tar,.gz,.tgz,.bz2,.xz"
+    };
+}
+
+/// What the containers of assets accept, every [`AssetKind`] but [`AssetKind::File`].
+pub const ACCEPT: &str = concat!("image/*,video/*,audio/*,application/pdf,", archives!());
+
+/// What a blob holds, guessed from its extension.
+/// Decides how it is previewed and what is inserted to show it in markdown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssetKind {
+    Image,
+    Video,
+    Audio,
+    Pdf,
+    Archive,
+    File,
+}
+
+impl AssetKind {
+    pub fn from_name(name: &str) -> Self {
+        let name = name.split(['?', '#']).next().unwrap_or_default();
+        let ext = match name.rsplit_once('.') {
+            Some((_, ext)) if !ext.contains('/') => ext.to_lowercase(),
+            _ => String::new(),
+        };
+        match ext.as_str() {
+            "png" | "jpg" | "jpeg" | "gif" | "webp" | "avif" | "svg" | "bmp" | "ico" => Self::Image,
+            "mp4" | "webm" | "ogv" | "mov" | "m4v" => Self::Video,
+            "mp3" | "ogg" | "oga" | "opus" | "wav" | "flac" | "m4a" => Self::Audio,
+            "pdf" => Self::Pdf,
+            ext if archives!().split(',').any(|a| a[1..] == *ext) => Self::Archive,
+            _ => Self::File,
+        }
+    }
+
+    /// Uses the MIME type when the browser knows it, the extension otherwise.
+    pub fn from_file(name: &str, mime: &str) -> Self {
+        match mime.split_once('/') {
+            Some(("image", _)) => Self::Image,
+            Some(("video", _)) => Self::Video,
+            Some(("audio", _)) => Self::Audio,
+            Some((_, "pdf")) => Self::Pdf,
+            _ => Self::from_name(name),
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Image => "/img/ui/image.svg",
+            Self::Video => "/img/ui/file-video.svg",
+            Self::Audio => "/img/ui/file-audio.svg",
+            Self::Pdf => "/img/ui/file-pdf.svg",
+            Self::Archive => "/img/ui/file-archive.svg",
+            Self::File => "/img/ui/file.svg",
+        }
+    }
+
+    /// Header making the browser save the blob as `name` instead of opening it.
+    /// Links to blobs are cross-origin, so their `download` attribute is ignored.
+    pub fn content_disposition(&self, name: &str) -> Option<String> {
+        let name = name.rsplit('/').next().unwrap_or(name);
+        match self {
+            Self::Archive | Self::File => Some(format!(
+                "attachment; filename=\"{}\"",
+                name.replace(['"', '\\'], "_")
+            )),
+            _ => None,
+        }
+    }
+
+    /// Shows the blob at `url` in a page, videos and audio need the html to be allowed.
+    pub fn markdown(&self, name: &str, url: &str) -> String {
+        let name = name.rsplit('/').next().unwrap_or(name);
+        let label = name.replace('[', "\\[").replace(']', "\\]");
+        match self {
+            Self::Image => format!("![{}]({})", label, url),
+            Self::Video => format!(
+                "<video controls preload=\"metadata\" src=\"{}\"></video>",
+                url
+            ),
+            Self::Audio => format!("<audio controls src=\"{}\"></audio>", url),
+            Self::Pdf => format!("[{}]({})", label, url),
+            Self::Archive | Self::File => format!(
+                "<a class=\"btn btn-primary not-prose\" href=\"{}\" download>{}</a>",
+                url,
+                escape_html(name)
+            ),
+        }
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,156 @@
+use super::{
+    blob::{invalidate_container, BlobClient},
+    client::{ApiClient, RequestError, UploadProgress},
+    editor::EditorClient,
+    memory::MemoryClient,
+    resource::ResourceClient,
+    revision::RevisionClient,
+    settings::SettingsClient,
+    user::{LoginResponse, UserClient},
+};
+use crate::data::config::{BackendKind, ConfigStore};
+use petompp_web_models::models::{
+    blob::blob_meta::{BlobMetaData, BlobUpload},
+    country::Country,
+    credentials::Credentials,
+    password_requirements::PasswordRequirements,
+    user::UserData,
+    username_requirements::UsernameRequirements,
+};
+use serde::de::DeserializeOwned;
+use yew::Callback;
+
+/// Client used by the pages, forwards calls to the backend selected in the config.
+/// Blob writes drop the cached responses of their container, whichever backend made them,
+/// which also tells the components showing it to fetch it again.
+pub struct Backend;
+
+macro_rules! dispatch {
+    ($trait:ident :: $fn:ident $(::<$t:ty>)? ($($arg:expr),*)) => {
+        match ConfigStore::get().backend {
+            BackendKind::Api => <ApiClient as $trait>::$fn$(::<$t>)?($($arg),*).await,
+            BackendKind::Memory => <MemoryClient as $trait>::$fn$(::<$t>)?($($arg),*).await,
+        }
+    };
+}
+
+#[yewdux::async_trait(?Send)]
+impl BlobClient for Backend {
+    async fn get_meta<TBlob: TryFrom<BlobMetaData> + DeserializeOwned>(
+        container: &str,
+        filename: &str,
+    ) -> Result<TBlob, RequestError> {
+        dispatch!(BlobClient::get_meta::<TBlob>(container, filename))
+    }
+    async fn get_meta_all<TBlob: TryFrom<BlobMetaData> + DeserializeOwned>(
+        container: &str,
+        prefix: Option<&str>,
+    ) -> Result<Vec<TBlob>, RequestError> {
+        dispatch!(BlobClient::get_meta_all::<TBlob>(container, prefix))
+    }
+    async fn get_names(container: &str, prefix: Option<&str>) -> Result<Vec<String>, RequestError> {
+        dispatch!(BlobClient::get_names(container, prefix))
+    }
+    async fn get_content(container: &str, filename: &str) -> Result<Vec<u8>, RequestError> {
+        dispatch!(BlobClient::get_content(container, filename))
+    }
+    async fn get_content_fresh(container: &str, filename: &str) -> Result<Vec<u8>, RequestError> {
+        dispatch!(BlobClient::get_content_fresh(container, filename))
+    }
+    async fn create_or_update_with_progress(
+        token: &str,
+        container: &str,
+        upload: &BlobUpload,
+        onprogress: Callback<UploadProgress>,
+    ) -> Result<String, RequestError> {
+        let result = dispatch!(BlobClient::create_or_update_with_progress(
+            token, container, upload, onprogress
+        ));
+        invalidate_container(container);
+        result
+    }
+    async fn delete(token: &str, container: &str, filename: &str) -> Result<(), RequestError> {
+        let result = dispatch!(BlobClient::delete(token, container, filename));
+        invalidate_container(container);
+        result
+    }
+    async fn rename(
+        token: &str,
+        container: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<(), RequestError> {
+        let result = dispatch!(BlobClient::rename(token, container, from, to));
+        invalidate_container(container);
+        result
+    }
+}
+
+#[yewdux::async_trait(?Send)]
+impl ResourceClient for Backend {
+    async fn create_resource(
+        token: &str,
+        key: &str,
+        lang: &Country,
+        value: &str,
+    ) -> Result<(), RequestError> {
+        dispatch!(ResourceClient::create_resource(token, key, lang, value))
+    }
+    async fn get_resource(key: &str, lang: &Country) -> Result<(Country, String), RequestError> {
+        dispatch!(ResourceClient::get_resource(key, lang))
+    }
+    async fn get_resource_keys(token: &str) -> Result<Vec<String>, RequestError> {
+        dispatch!(ResourceClient::get_resource_keys(token))
+    }
+    async fn update_resource(
+        token: &str,
+        key: &str,
+        lang: &Country,
+        value: &str,
+    ) -> Result<(), RequestError> {
+        dispatch!(ResourceClient::update_resource(token, key, lang, value))
+    }
+    async fn delete_resource(token: &str, key: &str) -> Result<(), RequestError> {
+        dispatch!(ResourceClient::delete_resource(token, key))
+    }
+    async fn delete_resource_lang(
+        token: &str,
+        key: &str,
+        lang: &Country,
+    ) -> Result<(), RequestError> {
+        dispatch!(ResourceClient::delete_resource_lang(token, key, lang))
+    }
+}
+
+#[yewdux::async_trait(?Send)]
+impl UserClient for Backend {
+    async fn login(credentials: Credentials) -> Result<LoginResponse, RequestError> {
+        dispatch!(UserClient::login(credentials))
+    }
+    async fn register(credentials: Credentials) -> Result<(), RequestError> {
+        dispatch!(UserClient::register(credentials))
+    }
+    async fn get_users(token: &str) -> Result<Vec<UserData>, RequestError> {
+        dispatch!(UserClient::get_users(token))
+    }
+    async fn activate_user(token: &str, id: i32) -> Result<(), RequestError> {
+        dispatch!(UserClient::activate_user(token, id))
+    }
+    async fn delete_user(token: &str, id: i32) -> Result<(), RequestError> {
+        dispatch!(UserClient::delete_user(token, id))
+    }
+}
+
+#[yewdux::async_trait(?Send)]
+impl SettingsClient for Backend {
+    async fn get_user_settings(
+    ) -> Result<(UsernameRequirements, PasswordRequirements), RequestError> {
+        dispatch!(SettingsClient::get_user_settings())
+    }
+}
+
+#[yewdux::async_trait(?Send)]
+impl EditorClient for Backend {}
+
+#[yewdux::async_trait(?Send)]
+impl RevisionClient for Backend {}
@@ -1,24 +1,28 @@
-use super::client::{ApiClient, RequestError};
+use super::{
+    cache::ResponseCache,
+    client::{ApiClient, RequestError, RequestOptions, UploadProgress},
+    storage::storage,
+};
+use crate::data::{
+    config::ConfigStore, resources::publication::Publishable, session::SessionStore,
+};
 use petompp_web_models::{
     error::Error,
-    models::blob::blob_meta::{BlobMetaData, BlobUpload},
+    models::blob::blob_meta::{BlobMetaData, BlobMetaDto, BlobUpload},
 };
-use reqwasm::http::{Method, Request};
-use serde::{de::DeserializeOwned, Deserialize};
-use web_sys::RequestCache;
-
-lazy_static::lazy_static! {
-    static ref AZURE_STORAGE_URL: String =
-        match std::option_env!("AZURE_STORAGE_URL").unwrap_or_default() {
-            url if url.ends_with('/') => url.to_string(),
-            url => format!("{}/", url)
-        };
-}
+use reqwasm::http::Method;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::time::Duration;
+use yew::Callback;
+use yewdux::prelude::Dispatch;
+
+/// Each block is retried on its own, so a slow connection only repeats the failed one.
+const BLOCK_TIMEOUT: Duration = Duration::from_secs(60);
 
 #[yewdux::async_trait(?Send)]
 pub trait BlobClient {
     fn get_url(container: &str, filename: &str) -> String {
-        format!("{}{}/{}", *AZURE_STORAGE_URL, container, filename)
+        storage().blob_url(container, filename)
     }
     async fn get_meta<TBlob: TryFrom<BlobMetaData> + DeserializeOwned>(
         container: &str,
@@ -34,12 +38,73 @@ pub trait BlobClient {
         let content = Self::get_content(container, filename).await?;
         String::from_utf8(content).map_err(|e| RequestError::Parse(e.to_string()))
     }
+    /// Skips the response cache, for reads whose result is written back.
+    async fn get_content_fresh(container: &str, filename: &str) -> Result<Vec<u8>, RequestError> {
+        Self::get_content(container, filename).await
+    }
+    async fn get_content_str_fresh(
+        container: &str,
+        filename: &str,
+    ) -> Result<String, RequestError> {
+        let content = Self::get_content_fresh(container, filename).await?;
+        String::from_utf8(content).map_err(|e| RequestError::Parse(e.to_string()))
+    }
     async fn create_or_update(
         token: &str,
         container: &str,
         upload: &BlobUpload,
+    ) -> Result<String, RequestError> {
+        Self::create_or_update_with_progress(token, container, upload, Callback::noop()).await
+    }
+    async fn create_or_update_with_progress(
+        token: &str,
+        container: &str,
+        upload: &BlobUpload,
+        onprogress: Callback<UploadProgress>,
     ) -> Result<String, RequestError>;
     async fn delete(token: &str, container: &str, filename: &str) -> Result<(), RequestError>;
+    /// Moves a blob, by copying it and deleting the original unless the backend can move it.
+    /// Fails when a blob already exists at `to`, instead of overwriting it.
+    async fn rename(
+        token: &str,
+        container: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<(), RequestError> {
+        // metadata isn't cached, so a blob added meanwhile by someone else is found too
+        match Self::get_meta::<BlobMetaData>(container, to).await {
+            Ok(_) => return Err(already_exists(to)),
+            Err(RequestError::Endpoint(404, _)) => (),
+            Err(e) => return Err(e),
+        }
+        let meta = Self::get_meta::<BlobMetaData>(container, from).await?;
+        let content = Self::get_content(container, from).await?;
+        let mut meta = BlobMetaDto::from(meta);
+        meta.filename = to.to_string();
+        Self::create_or_update(token, container, &BlobUpload { meta, content }).await?;
+        Self::delete(token, container, from).await
+    }
+    /// Moves every blob in the `from` folder to the `to` folder.
+    async fn rename_dir(
+        token: &str,
+        container: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<(), RequestError> {
+        let from = format!("{}/", from.trim_end_matches('/'));
+        let to = format!("{}/", to.trim_end_matches('/'));
+        // checked up front, so a folder isn't left half moved
+        match Self::get_names(container, Some(&to)).await {
+            Ok(names) if !names.is_empty() => return Err(already_exists(&to)),
+            Ok(_) | Err(RequestError::Endpoint(404, _)) => (),
+            Err(e) => return Err(e),
+        }
+        for name in Self::get_names(container, Some(&from)).await? {
+            let target = format!("{}{}", to, &name[from.len()..]);
+            Self::rename(token, container, &name, &target).await?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Deserialize)]
@@ -60,19 +125,24 @@ impl BlobClient for ApiClient {
         container: &str,
         filename: &str,
     ) -> Result<TBlob, RequestError> {
-        Self::send_json(
+        let meta: serde_json::Value = Self::send_json(
             Method::GET,
             format!("api/v1/blob/{}/{}", container, filename).as_str(),
             None,
             Option::<&String>::None,
         )
-        .await
+        .await?;
+        let blob = serde_json::from_value::<BlobMetaData>(meta.clone())
+            .map_err(|e| RequestError::Parse(e.to_string()))?;
+        check_visible(&blob)?;
+        serde_json::from_value(meta).map_err(|e| RequestError::Parse(e.to_string()))
     }
     async fn get_meta_all<TBlob: TryFrom<BlobMetaData> + DeserializeOwned>(
         container: &str,
         prefix: Option<&str>,
     ) -> Result<Vec<TBlob>, RequestError> {
-        Ok(Self::send_json::<FullResp<BlobMetaData>>(
+        Ok(Self::send_json_with::<FullResp<BlobMetaData>>(
+            RequestOptions::for_method(Method::GET).with_cache(Some(RequestOptions::CACHE_TTL)),
             Method::GET,
             format!(
                 "api/v1/blob/{}?data=full{}",
@@ -86,14 +156,15 @@ impl BlobClient for ApiClient {
         .await
         .map(|mut r| {
             r.full.sort_by(|a, b| a.filename.cmp(&b.filename));
-            r.full
+            listed(r.full)
         })?
         .into_iter()
         .filter_map(|b| TBlob::try_from(b).ok())
         .collect())
     }
     async fn get_names(container: &str, prefix: Option<&str>) -> Result<Vec<String>, RequestError> {
-        Self::send_json::<NameResp>(
+        Self::send_json_with::<NameResp>(
+            RequestOptions::for_method(Method::GET).with_cache(Some(RequestOptions::CACHE_TTL)),
             Method::GET,
             format!(
                 "api/v1/blob/{}?data=name{}",
@@ -111,37 +182,50 @@ impl BlobClient for ApiClient {
         })
     }
     async fn get_content(container: &str, filename: &str) -> Result<Vec<u8>, RequestError> {
-        let response = &Request::new(Self::get_url(container, filename).as_str())
-            .method(Method::GET)
-            .cache(RequestCache::NoCache)
-            .send()
-            .await
-            .map_err(|e| RequestError::Network(e.to_string()))?;
-
-        if response.status() == 404 {
-            return Err(RequestError::Endpoint(
-                404,
-                Error::Status(404, "Not found".to_string()),
-            ));
+        let response = Self::get_shared(
+            RequestOptions::for_method(Method::GET).with_cache(Some(RequestOptions::CACHE_TTL)),
+            Self::get_url(container, filename),
+        )
+        .await?;
+
+        match response.status {
+            200..=299 => Ok(response.body.to_vec()),
+            status => Err(status_error(status)),
         }
+    }
+    async fn get_content_fresh(container: &str, filename: &str) -> Result<Vec<u8>, RequestError> {
+        let response = Self::get_shared(
+            RequestOptions::for_method(Method::GET),
+            Self::get_url(container, filename),
+        )
+        .await?;
 
-        response
-            .binary()
-            .await
-            .map_err(|e| RequestError::Network(e.to_string()))
+        match response.status {
+            200..=299 => Ok(response.body.to_vec()),
+            status => Err(status_error(status)),
+        }
     }
-    async fn create_or_update(
+    async fn create_or_update_with_progress(
         token: &str,
         container: &str,
         upload: &BlobUpload,
+        onprogress: Callback<UploadProgress>,
     ) -> Result<String, RequestError> {
-        Self::send_multipart(
-            Method::POST,
-            format!("api/v1/blob/{}", container).as_str(),
-            Some(token),
-            upload,
-        )
-        .await
+        match ConfigStore::get().uploads.block_size() {
+            Some(block_size) if upload.content.len() > block_size => {
+                Self::upload_blocks(token, container, upload, block_size, onprogress).await
+            }
+            _ => {
+                Self::send_multipart(
+                    Method::POST,
+                    format!("api/v1/blob/{}", container).as_str(),
+                    Some(token),
+                    upload,
+                    onprogress,
+                )
+                .await
+            }
+        }
     }
     async fn delete(token: &str, container: &str, filename: &str) -> Result<(), RequestError> {
         Self::send_json(
@@ -154,3 +238,126 @@ impl BlobClient for ApiClient {
         .map(|_: String| ())
     }
 }
+
+/// Leaves out drafts and content waiting for its time, unless an admin is logged in.
+pub(crate) fn listed(metas: Vec<BlobMetaData>) -> Vec<BlobMetaData> {
+    if Dispatch::<SessionStore>::new().get().is_admin() {
+        return metas;
+    }
+    metas.into_iter().filter(|m| m.is_public()).collect()
+}
+
+/// Content waiting for its publish date is not found, unless an admin is logged in.
+pub(crate) fn check_visible(meta: &BlobMetaData) -> Result<(), RequestError> {
+    match meta.is_scheduled() && !Dispatch::<SessionStore>::new().get().is_admin() {
+        true => Err(status_error(404)),
+        false => Ok(()),
+    }
+}
+
+#[derive(Serialize)]
+struct CommitBlocks<'a> {
+    meta: &'a BlobMetaDto,
+    blocks: Vec<String>,
+}
+
+impl ApiClient {
+    /// Uploads the content in blocks and commits them in order once all are stored.
+    /// Blocks the API already has are skipped, so calling it again after a failed block
+    /// resumes the upload. Block ids depend on the content, so a changed file starts over.
+    async fn upload_blocks(
+        token: &str,
+        container: &str,
+        upload: &BlobUpload,
+        block_size: usize,
+        onprogress: Callback<UploadProgress>,
+    ) -> Result<String, RequestError> {
+        let path = format!(
+            "api/v1/blob/{}/blocks?filename={}",
+            container,
+            String::from(web_sys::js_sys::encode_uri_component(&upload.meta.filename))
+        );
+        let stored = match Self::send_json::<Vec<String>>(
+            Method::GET,
+            &path,
+            Some(token),
+            Option::<&String>::None,
+        )
+        .await
+        {
+            Ok(stored) => stored,
+            Err(RequestError::Endpoint(404, _)) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        let fingerprint = format!("{:016x}", fnv1a(&upload.content));
+        let total = upload.content.len() as u64;
+        let mut sent = 0;
+        let mut blocks = Vec::new();
+        for (i, chunk) in upload.content.chunks(block_size).enumerate() {
+            let block = format!("{}-{:06}", fingerprint, i);
+            if !stored.contains(&block) {
+                Self::send_bytes::<String>(
+                    RequestOptions::for_method(Method::PUT).with_timeout(Some(BLOCK_TIMEOUT)),
+                    Method::PUT,
+                    format!("{}&block={}", path, block).as_str(),
+                    Some(token),
+                    chunk,
+                )
+                .await?;
+            }
+            sent += chunk.len() as u64;
+            onprogress.emit(UploadProgress { sent, total });
+            blocks.push(block);
+        }
+        Self::send_json(
+            Method::POST,
+            &path,
+            Some(token),
+            Some(&CommitBlocks {
+                meta: &upload.meta,
+                blocks,
+            }),
+        )
+        .await
+    }
+}
+
+/// 64-bit FNV-1a, block ids have to match across builds and browsers to resume an upload,
+/// which `DefaultHasher` doesn't promise.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Storage answers with XML or nothing at all, so the error only tells the status apart.
+fn status_error(status: u16) -> RequestError {
+    let message = match status {
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not found",
+        429 => "Too many requests",
+        500..=599 => "Server error",
+        _ => "Unexpected status",
+    };
+    RequestError::Endpoint(status, Error::Status(status, message.to_string()))
+}
+
+pub(crate) fn already_exists(name: &str) -> RequestError {
+    RequestError::Endpoint(409, Error::Status(409, format!("{} already exists", name)))
+}
+
+/// Generation the container was last written in, watched by
+/// [`use_container_revision`](crate::hooks::container::use_container_revision).
+pub fn container_revision(container: &str) -> usize {
+    ResponseCache::invalidated_in(&<ApiClient as BlobClient>::get_url(container, ""))
+}
+
+/// Writes can change the listings, metadata and contents of the whole container.
+/// The storage url goes last, as [`container_revision`] reads it.
+pub(crate) fn invalidate_container(container: &str) {
+    let api_prefix = format!("{}api/v1/blob/{}", ConfigStore::get().api_url(), container);
+    ResponseCache::invalidate(&format!("{}/", api_prefix));
+    ResponseCache::invalidate(&format!("{}?", api_prefix));
+    ResponseCache::invalidate(&<ApiClient as BlobClient>::get_url(container, ""));
+}
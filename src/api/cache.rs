@@ -0,0 +1,169 @@
+use super::client::{RawResponse, RequestError};
+use futures::{
+    channel::oneshot,
+    future::{FutureExt, LocalBoxFuture, Shared},
+};
+use std::{cell::RefCell, collections::HashMap, future::Future, time::Duration};
+use web_sys::js_sys::Date;
+use yew::{platform::spawn_local, Callback};
+
+type Pending = Shared<LocalBoxFuture<'static, Result<RawResponse, RequestError>>>;
+
+struct Entry {
+    response: RawResponse,
+    /// Milliseconds since the epoch, as returned by `Date::now`.
+    fetched_at: f64,
+}
+
+/// GET responses keyed by URL, together with the requests still in flight.
+#[derive(Default)]
+pub struct ResponseCache {
+    entries: HashMap<String, Entry>,
+    pending: HashMap<String, (usize, Pending)>,
+    next_id: usize,
+    /// Bumped on every invalidation.
+    generation: usize,
+    /// Generation each prefix was last invalidated in, so responses to requests
+    /// started before a write to their resource don't end up in the cache.
+    invalidated: HashMap<String, usize>,
+    /// Called after every invalidation, see [`Self::subscribe`].
+    subscribers: HashMap<usize, Callback<()>>,
+}
+
+thread_local! {
+    static CACHE: RefCell<ResponseCache> = RefCell::new(ResponseCache::default());
+}
+
+impl ResponseCache {
+    pub fn fresh(key: &str, ttl: Duration) -> Option<RawResponse> {
+        CACHE.with(|c| {
+            c.borrow()
+                .entries
+                .get(key)
+                .filter(|e| Date::now() - e.fetched_at < ttl.as_millis() as f64)
+                .map(|e| e.response.clone())
+        })
+    }
+
+    pub fn etag(key: &str) -> Option<String> {
+        CACHE.with(|c| {
+            c.borrow()
+                .entries
+                .get(key)
+                .and_then(|e| e.response.etag.clone())
+        })
+    }
+
+    pub fn generation() -> usize {
+        CACHE.with(|c| c.borrow().generation)
+    }
+
+    /// Caches the response of a request started in `generation`,
+    /// unless its resource was invalidated since.
+    pub fn store(key: &str, response: RawResponse, generation: usize) {
+        CACHE.with(|c| {
+            let mut cache = c.borrow_mut();
+            if cache
+                .invalidated
+                .iter()
+                .any(|(prefix, g)| *g > generation && key.starts_with(prefix.as_str()))
+            {
+                return;
+            }
+            cache.entries.insert(
+                key.to_string(),
+                Entry {
+                    response,
+                    fetched_at: Date::now(),
+                },
+            );
+        })
+    }
+
+    /// Marks the cached response as fresh again, after `304 Not Modified`.
+    pub fn touch(key: &str) -> Option<RawResponse> {
+        CACHE.with(|c| {
+            c.borrow_mut().entries.get_mut(key).map(|e| {
+                e.fetched_at = Date::now();
+                e.response.clone()
+            })
+        })
+    }
+
+    /// Drops cached and in-flight responses for URLs starting with `prefix`.
+    pub fn invalidate(prefix: &str) {
+        let subscribers = CACHE.with(|c| {
+            let mut cache = c.borrow_mut();
+            cache.entries.retain(|k, _| !k.starts_with(prefix));
+            cache.pending.retain(|k, _| !k.starts_with(prefix));
+            cache.generation += 1;
+            let generation = cache.generation;
+            cache.invalidated.insert(prefix.to_string(), generation);
+            cache.subscribers.values().cloned().collect::<Vec<_>>()
+        });
+        for subscriber in subscribers {
+            subscriber.emit(());
+        }
+    }
+
+    /// Generation `prefix` was last invalidated in, 0 if it never was.
+    pub fn invalidated_in(prefix: &str) -> usize {
+        CACHE.with(|c| {
+            c.borrow()
+                .invalidated
+                .get(prefix)
+                .copied()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Calls `callback` after every invalidation, until it is unsubscribed with the returned id.
+    pub fn subscribe(callback: Callback<()>) -> usize {
+        CACHE.with(|c| {
+            let mut cache = c.borrow_mut();
+            let id = cache.next_id;
+            cache.next_id += 1;
+            cache.subscribers.insert(id, callback);
+            id
+        })
+    }
+
+    pub fn unsubscribe(id: usize) {
+        CACHE.with(|c| c.borrow_mut().subscribers.remove(&id));
+    }
+
+    /// Runs `fetch` unless the same key is already being fetched, then waits for that one.
+    /// The fetch is not tied to the caller's `AbortScope`, other callers may still need it.
+    pub async fn coalesce(
+        key: String,
+        fetch: impl Future<Output = Result<RawResponse, RequestError>> + 'static,
+    ) -> Result<RawResponse, RequestError> {
+        let pending = CACHE.with(|c| c.borrow().pending.get(&key).map(|(_, p)| p.clone()));
+        if let Some(pending) = pending {
+            return pending.await;
+        }
+        let (tx, rx) = oneshot::channel();
+        let pending = rx
+            .map(|r| r.unwrap_or(Err(RequestError::Network("Request dropped".to_string()))))
+            .boxed_local()
+            .shared();
+        let id = CACHE.with(|c| {
+            let mut cache = c.borrow_mut();
+            let id = cache.next_id;
+            cache.next_id += 1;
+            cache.pending.insert(key.clone(), (id, pending.clone()));
+            id
+        });
+        spawn_local(async move {
+            let result = fetch.await;
+            CACHE.with(|c| {
+                let mut cache = c.borrow_mut();
+                if cache.pending.get(&key).is_some_and(|(i, _)| *i == id) {
+                    cache.pending.remove(&key);
+                }
+            });
+            _ = tx.send(result);
+        });
+        pending.await
+    }
+}
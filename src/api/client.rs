@@ -1,10 +1,25 @@
-use crate::{data::session::SessionStore, pages::login::LoginRedirect};
+use super::{abort::AbortScope, cache::ResponseCache, memory::Fixtures};
+use crate::{
+    data::{
+        config::{Config, ConfigStore},
+        session::SessionStore,
+    },
+    pages::login::LoginRedirect,
+};
+use futures::{
+    channel::oneshot,
+    future::{self, Either},
+};
+use gloo::events::EventListener;
 use petompp_web_models::error::Error;
 use reqwasm::http::*;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
-use std::{collections::HashMap, fmt::Display};
-use yew::{html, virtual_dom::VNode};
+use std::{collections::HashMap, fmt::Display, future::Future, rc::Rc, time::Duration};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::js_sys::Uint8Array;
+use web_sys::{AbortController, AbortSignal, ProgressEvent, RequestCache, XmlHttpRequest};
+use yew::{html, virtual_dom::VNode, Callback};
 use yewdux::prelude::Dispatch;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -12,6 +27,7 @@ pub enum RequestError {
     Endpoint(u16, Error),
     Parse(String),
     Network(String),
+    Timeout,
 }
 
 impl Display for RequestError {
@@ -21,6 +37,14 @@ impl Display for RequestError {
 }
 
 impl RequestError {
+    /// Errors that are likely to go away on their own, like an API restart.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::Network(_) | Self::Timeout | Self::Endpoint(429 | 500..=599, _)
+        )
+    }
+
     pub fn handle_failed_auth(
         &self,
         session_dispatch: Dispatch<SessionStore>,
@@ -47,11 +71,8 @@ pub enum Response<T> {
 }
 
 impl<T: DeserializeOwned> Response<T> {
-    async fn from_response(value: reqwasm::http::Response) -> Result<Self, RequestError> {
-        let status_code = value.status();
-        let body = value
-            .json::<Value>()
-            .await
+    fn from_body(status_code: u16, body: &[u8]) -> Result<Self, RequestError> {
+        let body = serde_json::from_slice::<Value>(body)
             .map_err(|e| RequestError::Parse(e.to_string()))?;
         let status = body
             .get("status")
@@ -76,11 +97,60 @@ impl<T: DeserializeOwned> Response<T> {
     }
 }
 
-lazy_static::lazy_static! {
-    static ref API_URL: String = match std::option_env!("API_URL").unwrap_or_default() {
-        url if url.ends_with('/') => url.to_string(),
-        url => format!("{}/", url)
-    };
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UploadProgress {
+    /// Bytes of the request body sent so far.
+    pub sent: u64,
+    pub total: u64,
+}
+
+impl UploadProgress {
+    pub fn percent(&self) -> u64 {
+        match self.total {
+            0 => 0,
+            total => self.sent * 100 / total,
+        }
+    }
+}
+
+/// Response read into memory, so it can be shared by coalesced requests and cached.
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    pub status: u16,
+    pub etag: Option<String>,
+    pub body: Rc<Vec<u8>>,
+}
+
+impl RawResponse {
+    async fn read(response: reqwasm::http::Response) -> Result<Self, RequestError> {
+        Ok(Self {
+            status: response.status(),
+            etag: response.headers().get("ETag"),
+            body: Rc::new(
+                response
+                    .binary()
+                    .await
+                    .map_err(|e| RequestError::Network(e.to_string()))?,
+            ),
+        })
+    }
+
+    fn is_transient(&self) -> bool {
+        matches!(self.status, 429 | 500..=599)
+    }
+
+    fn parse<R: DeserializeOwned>(&self) -> Result<R, RequestError> {
+        match Response::from_body(self.status, &self.body) {
+            Ok(Response::Success(data)) => Ok(data),
+            Ok(Response::Error(s, e)) => Err(RequestError::Endpoint(s, e)),
+            // proxies in front of the API answer with non-JSON error pages
+            Err(RequestError::Parse(m)) if self.is_transient() => Err(RequestError::Endpoint(
+                self.status,
+                Error::Status(self.status, m),
+            )),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 trait Authorizable {
@@ -93,11 +163,108 @@ impl Authorizable for Request {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    /// Upper bound for a single wait, `Retry-After` values above it are not waited for.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(300),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub const NONE: Self = Self {
+        max_attempts: 1,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+    };
+
+    /// Only idempotent requests are retried by default.
+    pub fn for_method(method: Method) -> Self {
+        match method {
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS => {
+                Self::default()
+            }
+            _ => Self::NONE,
+        }
+    }
+
+    /// Delay before the next attempt or `None` if the request should not be retried.
+    fn next_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        if let Some(retry_after) = retry_after {
+            return (retry_after <= self.max_delay).then_some(retry_after);
+        }
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+        // "equal jitter", so parallel clients don't come back at the same time
+        let jitter = backoff
+            .div_f64(2.0)
+            .mul_f64(web_sys::js_sys::Math::random());
+        Some(backoff.div_f64(2.0) + jitter)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RequestOptions {
+    pub retry: RetryPolicy,
+    /// Applies to each attempt separately, `None` waits as long as the browser does.
+    pub timeout: Option<Duration>,
+    /// How long a GET response is reused before it is revalidated, `None` disables caching.
+    pub cache_ttl: Option<Duration>,
+}
+
+impl RequestOptions {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+    pub const UPLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+    pub const CACHE_TTL: Duration = Duration::from_secs(60);
+
+    /// Uploads create or replace the blob, so they are safe to retry,
+    /// and get long enough to finish on slow connections.
+    pub fn for_upload() -> Self {
+        Self {
+            retry: RetryPolicy::default(),
+            timeout: Some(Self::UPLOAD_TIMEOUT),
+            cache_ttl: None,
+        }
+    }
+
+    pub fn for_method(method: Method) -> Self {
+        Self {
+            retry: RetryPolicy::for_method(method),
+            timeout: Some(Self::DEFAULT_TIMEOUT),
+            cache_ttl: None,
+        }
+    }
+
+    pub fn with_cache(self, cache_ttl: Option<Duration>) -> Self {
+        Self { cache_ttl, ..self }
+    }
+
+    pub fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+}
+
 pub struct ApiClient;
 
 impl ApiClient {
     fn get_url(path: &str) -> String {
-        format!("{}{}", *API_URL, path)
+        format!("{}{}", ConfigStore::get().api_url(), path)
     }
 
     pub async fn send_json<R: DeserializeOwned>(
@@ -106,24 +273,62 @@ impl ApiClient {
         token: Option<&str>,
         body: Option<&impl Serialize>,
     ) -> Result<R, RequestError> {
-        let mut request = Request::new(Self::get_url(path).as_str()).method(method);
-        if let Some(token) = token {
-            request = request.authorize(token);
-        }
-        if let Some(body) = body {
-            request = request
-                .body(serde_json::to_string(body).map_err(|e| RequestError::Parse(e.to_string()))?);
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| RequestError::Network(e.to_string()))?;
+        Self::send_json_with(
+            RequestOptions::for_method(method),
+            method,
+            path,
+            token,
+            body,
+        )
+        .await
+    }
 
-        match Response::from_response(response).await? {
-            Response::Success(data) => Ok(data),
-            Response::Error(s, e) => Err(RequestError::Endpoint(s, e)),
+    pub async fn send_json_with<R: DeserializeOwned>(
+        options: RequestOptions,
+        method: Method,
+        path: &str,
+        token: Option<&str>,
+        body: Option<&impl Serialize>,
+    ) -> Result<R, RequestError> {
+        let url = Self::get_url(path);
+        if matches!(method, Method::GET) && token.is_none() && body.is_none() {
+            return Self::get_shared(options, url).await?.parse();
         }
+        let body = body
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| RequestError::Parse(e.to_string()))?;
+        Self::send(options, || {
+            let mut request = Request::new(url.as_str()).method(method);
+            if let Some(token) = token {
+                request = request.authorize(token);
+            }
+            if let Some(body) = &body {
+                request = request.body(body.clone());
+            }
+            request
+        })
+        .await
+    }
+
+    pub async fn send_bytes<R: DeserializeOwned>(
+        options: RequestOptions,
+        method: Method,
+        path: &str,
+        token: Option<&str>,
+        body: &[u8],
+    ) -> Result<R, RequestError> {
+        let url = Self::get_url(path);
+        Self::send(options, || {
+            let mut request = Request::new(url.as_str())
+                .method(method)
+                .header("Content-Type", "application/octet-stream");
+            if let Some(token) = token {
+                request = request.authorize(token);
+            }
+            request.body(Uint8Array::from(body))
+        })
+        .await
     }
 
     pub async fn send_multipart<R: DeserializeOwned>(
@@ -131,23 +336,261 @@ impl ApiClient {
         path: &str,
         token: Option<&str>,
         form: impl Into<FormData>,
+        onprogress: Callback<UploadProgress>,
     ) -> Result<R, RequestError> {
-        let mut request = Request::new(Self::get_url(path).as_str()).method(method);
-        if let Some(token) = token {
-            request = request.authorize(token);
-        }
+        Self::send_multipart_with(
+            RequestOptions::for_upload(),
+            method,
+            path,
+            token,
+            form,
+            onprogress,
+        )
+        .await
+    }
+
+    /// Sent through `XMLHttpRequest`, as `fetch` can't report upload progress,
+    /// with the same retries, timeout and cancelling as the other requests.
+    pub async fn send_multipart_with<R: DeserializeOwned>(
+        options: RequestOptions,
+        method: Method,
+        path: &str,
+        token: Option<&str>,
+        form: impl Into<FormData>,
+        onprogress: Callback<UploadProgress>,
+    ) -> Result<R, RequestError> {
+        let url = Self::get_url(path);
         let form = form.into();
-        let response = request
-            .body(form)
-            .send()
-            .await
-            .map_err(|e| RequestError::Network(e.to_string()))?;
+        Self::with_retries(options, |signal| {
+            send_xhr(method, &url, token, &form, onprogress.clone(), signal)
+        })
+        .await?
+        .parse()
+    }
+
+    /// GET that is merged with identical requests in flight and served from the cache
+    /// while fresh. Stale entries are revalidated with `If-None-Match`.
+    pub async fn get_shared(
+        options: RequestOptions,
+        url: String,
+    ) -> Result<RawResponse, RequestError> {
+        if let Some(ttl) = options.cache_ttl {
+            if let Some(response) = ResponseCache::fresh(&url, ttl) {
+                return Ok(response);
+            }
+        }
+        let fetch = {
+            let url = url.clone();
+            async move {
+                let generation = ResponseCache::generation();
+                let etag = options.cache_ttl.and_then(|_| ResponseCache::etag(&url));
+                let response = Self::send_raw(options, || {
+                    let mut request = Request::new(url.as_str())
+                        .method(Method::GET)
+                        .cache(RequestCache::NoCache);
+                    if let Some(etag) = &etag {
+                        request = request.header("If-None-Match", etag);
+                    }
+                    request
+                })
+                .await?;
+                match response.status {
+                    304 => ResponseCache::touch(&url).ok_or(RequestError::Parse(
+                        "Not modified, but not cached".to_string(),
+                    )),
+                    200..=299 if options.cache_ttl.is_some() => {
+                        ResponseCache::store(&url, response.clone(), generation);
+                        Ok(response)
+                    }
+                    _ => Ok(response),
+                }
+            }
+        };
+        ResponseCache::coalesce(url, fetch).await
+    }
+
+    async fn send<R: DeserializeOwned>(
+        options: RequestOptions,
+        request: impl Fn() -> Request,
+    ) -> Result<R, RequestError> {
+        Self::send_raw(options, request).await?.parse()
+    }
+
+    async fn send_raw(
+        options: RequestOptions,
+        request: impl Fn() -> Request,
+    ) -> Result<RawResponse, RequestError> {
+        Self::with_retries(options, |signal| {
+            Self::send_once(request().abort_signal(Some(&signal)))
+        })
+        .await
+    }
+
+    /// Runs the attempts until one succeeds or isn't worth retrying, cancelling each one
+    /// on its timeout or together with the component that started it.
+    async fn with_retries<
+        F: Future<Output = (Result<RawResponse, RequestError>, Option<Duration>)>,
+    >(
+        options: RequestOptions,
+        attempt_fn: impl Fn(AbortSignal) -> F,
+    ) -> Result<RawResponse, RequestError> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let controller = AbortController::new().unwrap();
+            // cancel the request together with the component that started it
+            let _scope_listener = AbortScope::current_signal().map(|scope| {
+                let controller = controller.clone();
+                EventListener::once(&scope, "abort", move |_| controller.abort())
+            });
+            let response = attempt_fn(controller.signal());
+            let (result, retry_after) = match options.timeout {
+                Some(timeout) => match future::select(
+                    Box::pin(response),
+                    Box::pin(async_std::task::sleep(timeout)),
+                )
+                .await
+                {
+                    Either::Left((response, _)) => response,
+                    Either::Right(_) => {
+                        controller.abort();
+                        (Err(RequestError::Timeout), None)
+                    }
+                },
+                None => response.await,
+            };
+            let transient = match &result {
+                Ok(response) => response.is_transient(),
+                Err(e) => e.is_transient(),
+            };
+            if transient {
+                if let Some(delay) = options.retry.next_delay(attempt, retry_after) {
+                    async_std::task::sleep(delay).await;
+                    continue;
+                }
+            }
+            return result;
+        }
+    }
+
+    async fn send_once(request: Request) -> (Result<RawResponse, RequestError>, Option<Duration>) {
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => return (Err(RequestError::Network(e.to_string())), None),
+        };
+        let retry_after = get_retry_after(&response);
+        (RawResponse::read(response).await, retry_after)
+    }
+}
 
-        match Response::from_response(response).await? {
-            Response::Success(data) => Ok(data),
-            Response::Error(s, e) => Err(RequestError::Endpoint(s, e)),
+/// One attempt of an upload, aborted through `signal`.
+async fn send_xhr(
+    method: Method,
+    url: &str,
+    token: Option<&str>,
+    form: &FormData,
+    onprogress: Callback<UploadProgress>,
+    signal: AbortSignal,
+) -> (Result<RawResponse, RequestError>, Option<Duration>) {
+    let network = |e: JsValue| RequestError::Network(format!("{:?}", e));
+    let xhr = match XmlHttpRequest::new() {
+        Ok(xhr) => xhr,
+        Err(e) => return (Err(network(e)), None),
+    };
+    let prepared = xhr
+        .open_with_async(method_name(method), url, true)
+        .and_then(|_| match token {
+            Some(token) => {
+                xhr.set_request_header("Authorization", format!("Bearer {}", token).as_str())
+            }
+            None => Ok(()),
+        })
+        .and_then(|_| xhr.upload());
+    let upload = match prepared {
+        Ok(upload) => upload,
+        Err(e) => return (Err(network(e)), None),
+    };
+    let _progress_listener = EventListener::new(&upload, "progress", move |e| {
+        let e = e.unchecked_ref::<ProgressEvent>();
+        onprogress.emit(UploadProgress {
+            sent: e.loaded() as u64,
+            total: e.total() as u64,
+        });
+    });
+    let (tx, rx) = oneshot::channel();
+    // fires after success, error and abort alike
+    let _done_listener = EventListener::once(&xhr, "loadend", move |_| _ = tx.send(()));
+    let _abort_listener = {
+        let xhr = xhr.clone();
+        EventListener::once(&signal, "abort", move |_| _ = xhr.abort())
+    };
+    if let Err(e) = xhr.send_with_opt_form_data(Some(form)) {
+        return (Err(network(e)), None);
+    }
+    _ = rx.await;
+    let status = match xhr.status() {
+        Ok(0) | Err(_) => {
+            return (
+                Err(RequestError::Network("Upload failed".to_string())),
+                None,
+            )
         }
+        Ok(status) => status,
+    };
+    let retry_after = match status {
+        429 | 503 => xhr
+            .get_response_header("Retry-After")
+            .ok()
+            .flatten()
+            .and_then(|v| parse_retry_after(&v)),
+        _ => None,
+    };
+    let response = RawResponse {
+        status,
+        etag: None,
+        body: Rc::new(
+            xhr.response_text()
+                .ok()
+                .flatten()
+                .unwrap_or_default()
+                .into_bytes(),
+        ),
+    };
+    (Ok(response), retry_after)
+}
+
+fn method_name(method: Method) -> &'static str {
+    match method {
+        Method::GET => "GET",
+        Method::HEAD => "HEAD",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::DELETE => "DELETE",
+        Method::CONNECT => "CONNECT",
+        Method::OPTIONS => "OPTIONS",
+        Method::TRACE => "TRACE",
+        Method::PATCH => "PATCH",
+    }
+}
+
+/// Reads `Retry-After` of 429 and 503 responses, both delay-seconds and HTTP-date forms.
+/// The API has to list the header in `Access-Control-Expose-Headers` for it to be visible.
+fn get_retry_after(response: &reqwasm::http::Response) -> Option<Duration> {
+    if !matches!(response.status(), 429 | 503) {
+        return None;
+    }
+    parse_retry_after(&response.headers().get("Retry-After")?)
+}
+
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
     }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
 }
 
 pub struct LocalClient;
@@ -167,4 +610,49 @@ impl LocalClient {
         serde_yaml::from_slice::<HashMap<String, String>>(&body)
             .map_err(|e| RequestError::Parse(e.to_string()))
     }
+
+    /// Tries `/config.json` and then `/config.yml`, YAML parser handles both formats.
+    /// Fails with the last error once every path has been tried.
+    pub async fn get_config() -> Result<Config, RequestError> {
+        let mut last_error = RequestError::Parse("no config file".to_string());
+        for path in ["/config.json", "/config.yml"] {
+            let resp = match Request::new(path).method(Method::GET).send().await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    last_error = RequestError::Network(e.to_string());
+                    continue;
+                }
+            };
+            if !resp.ok() {
+                continue;
+            }
+            let body = match resp.binary().await {
+                Ok(body) => body,
+                Err(e) => {
+                    last_error = RequestError::Network(e.to_string());
+                    continue;
+                }
+            };
+            // dev servers answer unknown paths with index.html
+            match serde_yaml::from_slice::<Config>(&body) {
+                Ok(config) => return Ok(config),
+                Err(e) => last_error = RequestError::Parse(e.to_string()),
+            }
+        }
+        Err(last_error)
+    }
+
+    pub async fn get_fixtures(path: &str) -> Result<Fixtures, RequestError> {
+        let resp = Request::new(path)
+            .method(Method::GET)
+            .send()
+            .await
+            .map_err(|e| RequestError::Network(e.to_string()))?;
+        let body = resp
+            .binary()
+            .await
+            .map_err(|e| RequestError::Network(e.to_string()))?;
+
+        serde_yaml::from_slice::<Fixtures>(&body).map_err(|e| RequestError::Parse(e.to_string()))
+    }
 }
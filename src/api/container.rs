@@ -0,0 +1,149 @@
+use super::{asset, backend::Backend, blob::BlobClient};
+use crate::data::resources::content::{ContentType, MetaKind};
+use petompp_web_models::models::{
+    blob::{
+        blob_meta::BlobMetaData, blog::BlogMetaData, markdown::MarkdownMeta,
+        project::ProjectMetaData,
+    },
+    country::Country,
+};
+use serde::de::DeserializeOwned;
+
+/// A blob container, adding one to the site starts with implementing this
+/// and listing it in [`CONTAINERS`].
+pub trait Container {
+    /// Name of the container in the storage.
+    const NAME: &'static str;
+    /// Files the blob browser uploads into it, as in the `accept` attribute of a file input.
+    const ACCEPT: &'static str;
+    /// Set for the content types besides the blog and the projects.
+    const CONTENT: Option<&'static ContentType> = None;
+    const INFO: ContainerInfo = ContainerInfo {
+        name: Self::NAME,
+        accept: Self::ACCEPT,
+        content: Self::CONTENT,
+    };
+    /// Metadata stored with its blobs.
+    type Meta: TryFrom<BlobMetaData> + DeserializeOwned + 'static;
+
+    fn url(path: &str) -> String {
+        <Backend as BlobClient>::get_url(Self::NAME, path)
+    }
+}
+
+/// Container holding markdown content, each piece in its own folder:
+/// `<id>/<lang>.md` for every translation, next to any files it uses.
+pub trait ContentContainer: Container {
+    fn dir(id: &str) -> String {
+        content_dir(id)
+    }
+
+    fn content_path(id: &str, lang: Country) -> String {
+        content_path(id, lang)
+    }
+}
+
+pub fn content_dir(id: &str) -> String {
+    format!("{}/", id)
+}
+
+pub fn content_path(id: &str, lang: Country) -> String {
+    MarkdownMeta::create_filename(id, lang)
+}
+
+/// Folder of the gallery of content shown like a project.
+pub fn images_dir(id: &str) -> String {
+    format!("{}images/", content_dir(id))
+}
+
+pub struct Blog;
+
+impl Container for Blog {
+    const NAME: &'static str = "blog";
+    const ACCEPT: &'static str = "";
+    type Meta = BlogMetaData;
+}
+
+impl ContentContainer for Blog {}
+
+pub struct Project;
+
+impl Container for Project {
+    const NAME: &'static str = "project";
+    const ACCEPT: &'static str = asset::ACCEPT;
+    type Meta = ProjectMetaData;
+}
+
+impl ContentContainer for Project {}
+
+impl Project {
+    /// Folder of the gallery, the splash image is one of its files.
+    pub fn images_dir(id: &str) -> String {
+        images_dir(id)
+    }
+}
+
+pub struct Notes;
+
+impl Container for Notes {
+    const NAME: &'static str = "notes";
+    const ACCEPT: &'static str = "";
+    const CONTENT: Option<&'static ContentType> = Some(&ContentType {
+        key: "notes",
+        container: Self::NAME,
+        meta: MetaKind::Blog,
+        title: "Notes",
+        item: "Note",
+        new_item: "NewNote",
+        back: "BackToNotes",
+    });
+    type Meta = BlogMetaData;
+}
+
+impl ContentContainer for Notes {}
+
+/// Images linked from markdown and used as blog post covers.
+pub struct ImageUpload;
+
+impl Container for ImageUpload {
+    const NAME: &'static str = "image-upload";
+    const ACCEPT: &'static str = asset::ACCEPT;
+    type Meta = BlobMetaData;
+}
+
+/// Saved states of the resources, see [`super::revision::RevisionClient`].
+/// Not listed in [`CONTAINERS`], it is only written by the editor.
+pub struct Revisions;
+
+impl Container for Revisions {
+    const NAME: &'static str = "revisions";
+    const ACCEPT: &'static str = "";
+    type Meta = BlobMetaData;
+}
+
+/// What is known about a container when only its name is at hand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContainerInfo {
+    pub name: &'static str,
+    pub accept: &'static str,
+    pub content: Option<&'static ContentType>,
+}
+
+impl ContainerInfo {
+    pub fn get(name: &str) -> Option<Self> {
+        CONTAINERS.iter().find(|c| c.name == name).copied()
+    }
+
+    /// Whether the blob browser can upload the file into the container.
+    pub fn accepts(&self, name: &str, mime: &str) -> bool {
+        self.accept.split(',').map(str::trim).any(|a| match a {
+            "" => false,
+            a if a.starts_with('.') => name.to_lowercase().ends_with(a),
+            a if a.ends_with("/*") => mime.starts_with(&a[..a.len() - 1]),
+            a => mime == a,
+        })
+    }
+}
+
+pub const CONTAINERS: [ContainerInfo; 4] =
+    [Blog::INFO, Project::INFO, ImageUpload::INFO, Notes::INFO];
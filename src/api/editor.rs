@@ -1,38 +1,44 @@
-use std::{path, str::FromStr};
-
 use super::{
     blob::BlobClient,
-    client::{ApiClient, RequestError},
+    client::RequestError,
+    container::{Blog, Container, ContentContainer, ImageUpload, Project},
     resource::ResourceClient,
+    usage::UsageIndex,
 };
 use crate::{
-    data::resources::id::{BlobType, ResId},
+    data::resources::{
+        content::{ContentMeta, ContentType, MetaKind},
+        id::{BlobType, ResId},
+        publication::{Publishable, Scheduled},
+        store::LocalStore,
+    },
     pages::editor::EditorData,
 };
-use petompp_web_models::models::{
-    blob::{blog::BlogMetaData, project::ProjectMetaData},
-    country::Country,
+use chrono::{DateTime, Utc};
+use petompp_web_models::{
+    error::Error,
+    models::{
+        blob::{
+            blob_meta::{BlobMetaData, BlobMetaDto, BlobUpload},
+            blog::BlogMetaData,
+            project::ProjectMetaData,
+        },
+        country::{into_iter, Country},
+    },
 };
+use serde::de::DeserializeOwned;
+use yewdux::prelude::Dispatch;
 
 #[yewdux::async_trait(?Send)]
 pub trait EditorClient: BlobClient + ResourceClient {
-    async fn get_res_ids(token: &str)
-        -> Result<(Vec<ResId>, Vec<ResId>, Vec<ResId>), RequestError>;
-    async fn get_data(
-        blob_type: &BlobType,
-        lang: Country,
-    ) -> Result<Option<EditorData>, RequestError>;
-}
-
-#[yewdux::async_trait(?Send)]
-impl EditorClient for ApiClient {
+    /// Resource keys, blog posts, projects and the enabled content types' ids.
     async fn get_res_ids(
         token: &str,
-    ) -> Result<(Vec<ResId>, Vec<ResId>, Vec<ResId>), RequestError> {
+    ) -> Result<(Vec<ResId>, Vec<ResId>, Vec<ResId>, Vec<ResId>), RequestError> {
         let (res_keys, blog_posts, projects) = futures::join!(
             Self::get_resource_keys(token),
-            Self::get_meta_all::<BlogMetaData>("blog", None),
-            Self::get_meta_all::<ProjectMetaData>("project", None)
+            Self::get_meta_all::<BlogMetaData>(Blog::NAME, None),
+            Self::get_meta_all::<ProjectMetaData>(Project::NAME, None)
         );
         let res_keys = not_found_as_empty(res_keys)?;
         let mut blog_posts = not_found_as_empty(blog_posts)?;
@@ -41,6 +47,17 @@ impl EditorClient for ApiClient {
         let mut projects = not_found_as_empty(projects)?;
         projects.sort_by(|a, b| a.filename.cmp(&b.filename));
         projects.dedup_by_key(|p| p.id().to_string());
+        let mut content = Vec::new();
+        for content_type in ContentType::enabled() {
+            let mut metas = not_found_as_empty(Self::get_content_metas(content_type).await)?;
+            metas.sort_by_key(|m| m.filename());
+            metas.dedup_by_key(|m| m.id());
+            content.extend(
+                metas
+                    .into_iter()
+                    .map(|m| ResId::Blob(BlobType::Content(content_type.key.to_string(), m.id()))),
+            );
+        }
         Ok((
             res_keys.into_iter().map(ResId::ResKey).collect(),
             blog_posts
@@ -51,64 +68,307 @@ impl EditorClient for ApiClient {
                 .into_iter()
                 .map(|p| ResId::Blob(BlobType::Project(p.id().to_string())))
                 .collect(),
+            content,
         ))
     }
+    /// What is saved for the resource, `None` when it has nothing in the language yet.
+    /// Read past the response cache, as it is compared with and saved over.
+    async fn get_saved(resid: &ResId, lang: Country) -> Result<Option<EditorData>, RequestError> {
+        match resid {
+            ResId::Blob(blob_type) => Self::get_data(blob_type, lang).await,
+            ResId::ResKey(key) => match Self::get_resource(key, &lang).await {
+                Ok((c, value)) if c == lang => Ok(Some(EditorData::Resource(value))),
+                Ok(_) | Err(RequestError::Endpoint(404, _)) => Ok(None),
+                Err(e) => Err(e),
+            },
+        }
+    }
+    /// Uploads the data, creating the resource key if it doesn't exist yet.
+    async fn save_data(
+        token: &str,
+        resid: &ResId,
+        lang: Country,
+        data: &EditorData,
+    ) -> Result<(), RequestError> {
+        let (container, meta, content) = match (resid, data) {
+            (ResId::ResKey(key), EditorData::Resource(value)) => {
+                return match Self::get_resource(key, &lang).await {
+                    Ok(_) => Self::update_resource(token, key, &lang, value).await,
+                    Err(RequestError::Endpoint(404, _)) => {
+                        Self::create_resource(token, key, &lang, value).await
+                    }
+                    Err(e) => Err(e),
+                };
+            }
+            (ResId::Blob(blob), EditorData::Blog((value, meta))) => {
+                (blob.container()?, (**meta).clone(), value)
+            }
+            (ResId::Blob(blob), EditorData::Project((value, meta))) => {
+                (blob.container()?, (**meta).clone(), value)
+            }
+            _ => {
+                return Err(RequestError::Parse(
+                    "data doesn't match the resource".to_string(),
+                ))
+            }
+        };
+        let mut meta = meta;
+        // only publishing changes the status, a draft may have started before it did
+        match Self::get_meta::<BlobMetaData>(container, &meta.filename).await {
+            Ok(saved) => meta.set_status(saved.is_draft(), saved.publish_at()),
+            Err(RequestError::Endpoint(404, _)) => (),
+            Err(e) => return Err(e),
+        }
+        Self::upload_data(token, container, meta, content).await
+    }
+    /// Sets the status of the saved version, leaving any draft of it aside.
+    /// Returns the saved data with the new status, read again for its update time.
+    async fn set_status(
+        token: &str,
+        resid: &ResId,
+        lang: Country,
+        draft: bool,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> Result<EditorData, RequestError> {
+        let ResId::Blob(blob) = resid else {
+            return Err(RequestError::Parse("resources have no status".to_string()));
+        };
+        let Some(mut data) = Self::get_saved(resid, lang).await? else {
+            return Err(RequestError::Endpoint(
+                404,
+                Error::Status(404, "Not found".to_string()),
+            ));
+        };
+        let meta = match &mut data {
+            EditorData::Blog((_, meta)) => {
+                meta.set_status(draft, publish_at);
+                (**meta).clone()
+            }
+            EditorData::Project((_, meta)) => {
+                meta.set_status(draft, publish_at);
+                (**meta).clone()
+            }
+            EditorData::Resource(_) => {
+                return Err(RequestError::Parse(
+                    "data doesn't match the resource".to_string(),
+                ))
+            }
+        };
+        Self::upload_data(token, blob.container()?, meta, &data.to_string()).await?;
+        Self::get_saved(resid, lang)
+            .await?
+            .ok_or_else(|| RequestError::Endpoint(404, Error::Status(404, "Not found".to_string())))
+    }
+    async fn upload_data(
+        token: &str,
+        container: &str,
+        meta: BlobMetaData,
+        content: &str,
+    ) -> Result<(), RequestError> {
+        let upload = BlobUpload {
+            meta: BlobMetaDto::from(meta),
+            content: content.as_bytes().to_vec(),
+        };
+        Self::create_or_update(token, container, &upload)
+            .await
+            .map(|_| ())
+    }
     async fn get_data(
         blob_type: &BlobType,
         lang: Country,
     ) -> Result<Option<EditorData>, RequestError> {
-        match blob_type {
-            BlobType::Blog(id) => {
-                let filename = format!("{}/{}.md", id, lang.key());
-                let container = "blog";
-                match ApiClient::get_meta(container, filename.as_str()).await {
-                    Ok(m) => Ok(Some(EditorData::Blog((
-                        ApiClient::get_content_str(container, filename.as_str()).await?,
-                        m,
-                    )))),
-                    // does it exist in another language?
-                    Err(RequestError::Endpoint(404, _)) => {
-                        let path = path::PathBuf::from_str(&filename)
-                            .map_err(|e| RequestError::Parse(e.to_string()))?;
-                        let prefix = path
-                            .parent()
-                            .and_then(|p| p.to_str())
-                            .ok_or(RequestError::Parse("file has no parent".to_string()))?;
-                        Ok(ApiClient::get_names(container, Some(prefix))
-                            .await
-                            .map(|_| None)?)
+        Ok(match blob_type {
+            BlobType::Blog(id) => Self::get_content_data::<Blog>(id, lang)
+                .await?
+                .map(EditorData::Blog),
+            BlobType::Project(id) => Self::get_content_data::<Project>(id, lang)
+                .await?
+                .map(EditorData::Project),
+            BlobType::Content(kind, id) => {
+                let c = ContentType::get(kind)
+                    .ok_or_else(|| RequestError::Parse(format!("unknown content type {}", kind)))?;
+                let (dir, filename) = (c.dir(id), c.content_path(id, lang));
+                match c.meta {
+                    MetaKind::Blog => {
+                        Self::get_markdown_data::<BlogMetaData>(c.container, &dir, &filename)
+                            .await?
+                            .map(EditorData::Blog)
+                    }
+                    MetaKind::Project => {
+                        Self::get_markdown_data::<ProjectMetaData>(c.container, &dir, &filename)
+                            .await?
+                            .map(EditorData::Project)
                     }
-                    Err(e) => Err(e),
                 }
             }
-            BlobType::Project(id) => {
-                let filename = format!("{}/{}.md", id, lang.key());
-                let container = "project";
-                match ApiClient::get_meta(container, filename.as_str()).await {
-                    Ok(m) => Ok(Some(EditorData::Project((
-                        ApiClient::get_content_str(container, filename.as_str()).await?,
-                        m,
-                    )))),
-                    // does it exist in another language?
-                    Err(RequestError::Endpoint(404, _)) => {
-                        let path = path::PathBuf::from_str(&filename)
-                            .map_err(|e| RequestError::Parse(e.to_string()))?;
-                        let prefix = path
-                            .parent()
-                            .and_then(|p| p.to_str())
-                            .ok_or(RequestError::Parse("file has no parent".to_string()))?;
-                        Ok(ApiClient::get_names(container, Some(prefix))
-                            .await
-                            .map(|_| None)?)
+        })
+    }
+    /// Metadata of every piece of the content type, in every language.
+    async fn get_content_metas(
+        content_type: &ContentType,
+    ) -> Result<Vec<ContentMeta>, RequestError> {
+        Ok(match content_type.meta {
+            MetaKind::Blog => Self::get_meta_all::<BlogMetaData>(content_type.container, None)
+                .await?
+                .into_iter()
+                .map(ContentMeta::Blog)
+                .collect(),
+            MetaKind::Project => {
+                Self::get_meta_all::<ProjectMetaData>(content_type.container, None)
+                    .await?
+                    .into_iter()
+                    .map(ContentMeta::Project)
+                    .collect()
+            }
+        })
+    }
+    async fn get_content_data<C: ContentContainer>(
+        id: &str,
+        lang: Country,
+    ) -> Result<Option<(String, C::Meta)>, RequestError> {
+        Self::get_markdown_data::<C::Meta>(C::NAME, &C::dir(id), &C::content_path(id, lang)).await
+    }
+    /// `None` if there is no translation at `filename`, but something else is in `dir`.
+    async fn get_markdown_data<M: TryFrom<BlobMetaData> + DeserializeOwned + 'static>(
+        container: &str,
+        dir: &str,
+        filename: &str,
+    ) -> Result<Option<(String, M)>, RequestError> {
+        match Self::get_meta::<M>(container, filename).await {
+            // the editor writes it back, so a cached older version would be saved over
+            Ok(m) => Ok(Some((
+                Self::get_content_str_fresh(container, filename).await?,
+                m,
+            ))),
+            // does it exist in another language?
+            Err(RequestError::Endpoint(404, _)) => {
+                Ok(Self::get_names(container, Some(dir)).await.map(|_| None)?)
+            }
+            Err(e) => Err(e),
+        }
+    }
+    /// Scans the markdown of every resource in every language, together with
+    /// the images set in the blog post, content and project metadata, and the drafts
+    /// kept in this browser. Content types turned off in the config are scanned too, their
+    /// blobs are still referenced. Resources that fail to load are skipped, see [`UsageIndex::skipped`].
+    async fn get_usage(token: &str) -> Result<UsageIndex, RequestError> {
+        let (res_keys, blog_posts, projects) = futures::join!(
+            Self::get_resource_keys(token),
+            Self::get_meta_all::<BlogMetaData>(Blog::NAME, None),
+            Self::get_meta_all::<ProjectMetaData>(Project::NAME, None)
+        );
+        let mut index = UsageIndex::default();
+        for key in not_found_as_empty(res_keys)? {
+            let res_id = ResId::ResKey(key.clone());
+            for lang in Country::iter() {
+                match Self::get_resource(&key, &lang).await {
+                    Ok((l, value)) if l == lang => index.scan(&res_id, &value),
+                    Ok(_) | Err(RequestError::Endpoint(404, _)) => (),
+                    Err(e) => index.skip(&res_id, e),
+                }
+            }
+        }
+        for meta in not_found_as_empty(blog_posts)? {
+            let res_id = ResId::Blob(BlobType::Blog(meta.id().to_string()));
+            index.add(Blog::NAME, &Blog::dir(meta.id()), &res_id);
+            if !meta.image().is_empty() {
+                index.add(ImageUpload::NAME, &meta.image(), &res_id);
+            }
+            match Self::get_content_str(Blog::NAME, &meta.filename).await {
+                Ok(content) => index.scan(&res_id, &content),
+                Err(e) => index.skip(&res_id, e),
+            }
+        }
+        for content_type in ContentType::all() {
+            let metas = Self::get_content_metas(content_type).await;
+            for meta in not_found_as_empty(metas)? {
+                let res_id =
+                    ResId::Blob(BlobType::Content(content_type.key.to_string(), meta.id()));
+                index.add(
+                    content_type.container,
+                    &content_type.dir(&meta.id()),
+                    &res_id,
+                );
+                if let Some(image) = meta.image() {
+                    index.add(ImageUpload::NAME, &image, &res_id);
+                }
+                match Self::get_content_str(content_type.container, &meta.filename()).await {
+                    Ok(content) => index.scan(&res_id, &content),
+                    Err(e) => index.skip(&res_id, e),
+                }
+            }
+        }
+        for meta in not_found_as_empty(projects)? {
+            let res_id = ResId::Blob(BlobType::Project(meta.id().to_string()));
+            // the gallery shows everything in the project folder
+            index.add(Project::NAME, &Project::dir(meta.id()), &res_id);
+            match Self::get_content_str(Project::NAME, &meta.filename).await {
+                Ok(content) => index.scan(&res_id, &content),
+                Err(e) => index.skip(&res_id, e),
+            }
+        }
+        // unsaved drafts can use files nothing saved links to yet
+        for entry in Dispatch::<LocalStore>::new().get().entries().into_values() {
+            let res_id = entry.resid;
+            match &res_id {
+                ResId::Blob(BlobType::Blog(id)) => index.add(Blog::NAME, &Blog::dir(id), &res_id),
+                ResId::Blob(BlobType::Project(id)) => {
+                    index.add(Project::NAME, &Project::dir(id), &res_id)
+                }
+                ResId::Blob(BlobType::Content(key, id)) => {
+                    if let Some(content_type) = ContentType::get(key) {
+                        index.add(content_type.container, &content_type.dir(id), &res_id)
                     }
-                    Err(e) => Err(e),
                 }
+                ResId::ResKey(_) => (),
+            }
+            if let EditorData::Blog((_, meta)) = &entry.data {
+                if !meta.image().is_empty() {
+                    index.add(ImageUpload::NAME, &meta.image(), &res_id);
+                }
+            }
+            index.scan(&res_id, &entry.data.to_string());
+        }
+        Ok(index)
+    }
+    /// Resources linking to the blob, or anything in the folder if `path` ends with `/`.
+    async fn find_references(
+        token: &str,
+        container: &str,
+        path: &str,
+    ) -> Result<Vec<ResId>, RequestError> {
+        Ok(Self::get_usage(token).await?.used_by(container, path))
+    }
+    /// Published content waiting for its time, the soonest first.
+    async fn get_scheduled() -> Result<Vec<Scheduled>, RequestError> {
+        let (blog_posts, projects) = futures::join!(
+            Self::get_meta_all::<BlogMetaData>(Blog::NAME, None),
+            Self::get_meta_all::<ProjectMetaData>(Project::NAME, None)
+        );
+        let mut scheduled = Vec::new();
+        for meta in not_found_as_empty(blog_posts)? {
+            let id = BlobType::Blog(meta.id().to_string());
+            scheduled.extend(Scheduled::new(&meta, id, meta.lang(), meta.title()));
+        }
+        for meta in not_found_as_empty(projects)? {
+            let id = BlobType::Project(meta.id().to_string());
+            scheduled.extend(Scheduled::new(&meta, id, meta.lang(), meta.title()));
+        }
+        for content_type in ContentType::enabled() {
+            let metas = not_found_as_empty(Self::get_content_metas(content_type).await)?;
+            for meta in metas {
+                let id = BlobType::Content(content_type.key.to_string(), meta.id());
+                scheduled.extend(Scheduled::new(&meta, id, meta.lang(), &meta.title()));
             }
         }
+        scheduled.sort_by_key(|s| s.publish_at);
+        Ok(scheduled)
     }
 }
 
-fn not_found_as_empty<T>(e: Result<Vec<T>, RequestError>) -> Result<Vec<T>, RequestError> {
+pub(crate) fn not_found_as_empty<T>(
+    e: Result<Vec<T>, RequestError>,
+) -> Result<Vec<T>, RequestError> {
     match e {
         Ok(v) => Ok(v),
         Err(RequestError::Endpoint(404, _)) => Ok(vec![]),
@@ -0,0 +1,221 @@
+use super::{
+    asset::AssetKind,
+    backend::Backend,
+    blob::BlobClient,
+    client::{RequestError, UploadProgress},
+};
+use crate::data::config::ConfigStore;
+use futures::channel::oneshot;
+use petompp_web_models::models::blob::blob_meta::BlobUpload;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    js_sys::Array, Blob, CanvasRenderingContext2d, File, FilePropertyBag, HtmlCanvasElement,
+    ImageBitmap,
+};
+use yew::Callback;
+
+const THUMBNAIL_INFIX: &str = ".thumb.";
+/// Formats [`FORMAT`] can be, on any browser that uploaded the images.
+const ENCODED_FORMATS: [&str; 2] = ["webp", "png"];
+const CONTENT_DISPOSITION: &str = "content_disposition";
+
+thread_local! {
+    /// Format images are re-encoded to, browsers without a WebP encoder fall back to PNG.
+    static FORMAT: &'static str = match webp_supported() {
+        true => "webp",
+        false => "png",
+    };
+}
+
+/// Path of the thumbnail stored next to the image at `path`, like `a.png.thumb.png`.
+/// Thumbnails are encoded together with their images, so they share the format,
+/// images in other formats get the current [`FORMAT`].
+pub fn thumbnail_path(path: &str) -> String {
+    let format = path[strip_extension(path).len()..]
+        .strip_prefix('.')
+        .filter(|ext| ENCODED_FORMATS.contains(ext))
+        .unwrap_or_else(|| FORMAT.with(|f| *f));
+    format!("{}{}{}", path, THUMBNAIL_INFIX, format)
+}
+
+pub fn is_thumbnail(path: &str) -> bool {
+    ENCODED_FORMATS.iter().any(|format| {
+        path.strip_suffix(format)
+            .map_or(false, |rest| rest.ends_with(THUMBNAIL_INFIX))
+    })
+}
+
+/// Path the image uploaded as `name` ends up at, so collisions can be checked before uploading.
+pub fn upload_name(name: &str, file: &File) -> String {
+    match is_kept_as_is(file) {
+        true => name.to_string(),
+        false => format!("{}.{}", strip_extension(name), FORMAT.with(|f| *f)),
+    }
+}
+
+/// Downscales and re-encodes the image, then uploads it together with its thumbnail.
+/// Returns the path of the uploaded image, see [`upload_name`].
+/// Vector and animated images, like any other files, are uploaded as they are.
+pub async fn upload_image(
+    token: &str,
+    container: &str,
+    name: &str,
+    file: &File,
+    onprogress: Callback<UploadProgress>,
+) -> Result<String, RequestError> {
+    let error = |e: JsValue| RequestError::Parse(format!("{:?}", e));
+    if is_kept_as_is(file) {
+        return upload_file(token, container, file, name, onprogress).await;
+    }
+    let config = ConfigStore::get().images;
+    let quality = config.quality.clamp(0.0, 1.0);
+    let name = upload_name(name, file);
+    let bitmap: ImageBitmap = JsFuture::from(
+        web_sys::window()
+            .unwrap()
+            .create_image_bitmap_with_blob(file)
+            .map_err(error)?,
+    )
+    .await
+    .map_err(error)?
+    .unchecked_into();
+    let (width, height) = fit(&bitmap, config.max_width, config.max_height);
+    let image = encode(&bitmap, width, height, quality)
+        .await
+        .map_err(error)?;
+    let (width, height) = fit(&bitmap, config.thumbnail_size, config.thumbnail_size);
+    let thumbnail = encode(&bitmap, width, height, quality)
+        .await
+        .map_err(error)?;
+    bitmap.close();
+    let image = to_file(&image, &name).map_err(error)?;
+    let thumbnail = to_file(&thumbnail, &thumbnail_path(&name)).map_err(error)?;
+
+    upload_file(token, container, &image, &name, onprogress).await?;
+    upload_file(
+        token,
+        container,
+        &thumbnail,
+        &thumbnail_path(&name),
+        Callback::noop(),
+    )
+    .await?;
+    Ok(name)
+}
+
+async fn upload_file(
+    token: &str,
+    container: &str,
+    file: &File,
+    name: &str,
+    onprogress: Callback<UploadProgress>,
+) -> Result<String, RequestError> {
+    let mut upload = BlobUpload::from_file(file)
+        .await
+        .map_err(|e| RequestError::Network(e.to_string()))?;
+    upload.meta.filename = name.to_string();
+    if let Some(disposition) = AssetKind::from_file(name, &file.type_()).content_disposition(name) {
+        // set by the API as the header of the blob
+        upload
+            .meta
+            .metadata
+            .insert(CONTENT_DISPOSITION.to_string(), disposition);
+    }
+    Backend::create_or_update_with_progress(token, container, &upload, onprogress).await?;
+    Ok(upload.meta.filename)
+}
+
+fn is_kept_as_is(file: &File) -> bool {
+    AssetKind::from_file(&file.name(), &file.type_()) != AssetKind::Image
+        || matches!(file.type_().as_str(), "image/svg+xml" | "image/gif")
+}
+
+pub fn strip_extension(path: &str) -> &str {
+    let name_start = path.rfind('/').map(|i| i + 1).unwrap_or_default();
+    match path[name_start..].rfind('.') {
+        Some(0) | None => path,
+        Some(i) => &path[..name_start + i],
+    }
+}
+
+/// Size within the bounds keeping the aspect ratio, images are never upscaled.
+fn fit(bitmap: &ImageBitmap, max_width: u32, max_height: u32) -> (u32, u32) {
+    let (width, height) = (bitmap.width() as f64, bitmap.height() as f64);
+    let scale = (max_width as f64 / width)
+        .min(max_height as f64 / height)
+        .min(1.0);
+    (
+        ((width * scale).round() as u32).max(1),
+        ((height * scale).round() as u32).max(1),
+    )
+}
+
+/// Browsers without an encoder for a format return PNG data urls instead.
+fn webp_supported() -> bool {
+    web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.create_element("canvas").ok())
+        .and_then(|c| {
+            c.unchecked_into::<HtmlCanvasElement>()
+                .to_data_url_with_type("image/webp")
+                .ok()
+        })
+        .map_or(false, |url| url.starts_with("data:image/webp"))
+}
+
+/// Draws the bitmap on a canvas and encodes it in the [`FORMAT`].
+async fn encode(
+    bitmap: &ImageBitmap,
+    width: u32,
+    height: u32,
+    quality: f64,
+) -> Result<Blob, JsValue> {
+    let canvas: HtmlCanvasElement = web_sys::window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .create_element("canvas")?
+        .unchecked_into();
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("canvas is not supported"))?
+        .unchecked_into();
+    context.draw_image_with_image_bitmap_and_dw_and_dh(
+        bitmap,
+        0.0,
+        0.0,
+        width as f64,
+        height as f64,
+    )?;
+    let (tx, rx) = oneshot::channel();
+    let callback = Closure::once(move |blob: Option<Blob>| _ = tx.send(blob));
+    let format = format!("image/{}", FORMAT.with(|f| *f));
+    canvas.to_blob_with_type_and_encoder_options(
+        callback.as_ref().unchecked_ref(),
+        &format,
+        &JsValue::from_f64(quality),
+    )?;
+    let blob = rx
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(|| JsValue::from_str("image encoding failed"))?;
+    // a different format would end up at a name that wasn't checked for collisions
+    match blob.type_() == format {
+        true => Ok(blob),
+        false => Err(JsValue::from_str(&format!(
+            "encoded as {} instead of {}",
+            blob.type_(),
+            format
+        ))),
+    }
+}
+
+fn to_file(blob: &Blob, name: &str) -> Result<File, JsValue> {
+    let mut options = FilePropertyBag::new();
+    options.type_(&blob.type_());
+    File::new_with_blob_sequence_and_options(&Array::of1(blob), name, &options)
+}
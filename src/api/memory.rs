@@ -0,0 +1,493 @@
+use super::{
+    blob::{already_exists, check_visible, listed, BlobClient},
+    client::{ApiClient, RequestError, UploadProgress},
+    resource::ResourceClient,
+    settings::SettingsClient,
+    user::LoginResponse,
+    user::UserClient,
+};
+use petompp_web_models::{
+    error::{Error, UserError},
+    models::{
+        blob::blob_meta::{BlobMetaData, BlobUpload},
+        country::Country,
+        credentials::Credentials,
+        password_requirements::PasswordRequirements,
+        user::UserData,
+        user_settings_dto::UserSettingsDto,
+        username_requirements::UsernameRequirements,
+    },
+};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use serde_json::Value;
+use std::{cell::RefCell, collections::BTreeMap};
+use yew::Callback;
+
+/// Seed data of the in-memory backend.
+/// Blob metadata and users use the same JSON shape as the API responses.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Fixtures {
+    /// container -> filename -> blob
+    pub blobs: BTreeMap<String, BTreeMap<String, FixtureBlob>>,
+    /// resource key -> language key -> value
+    pub resources: BTreeMap<String, BTreeMap<String, String>>,
+    pub users: Vec<UserData>,
+    pub settings: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FixtureBlob {
+    pub meta: Value,
+    /// Read from the storage when left out, see [`super::storage`].
+    /// Written as text in the fixtures, uploads keep their bytes as they are.
+    #[serde(default, deserialize_with = "text_content")]
+    pub content: Option<Vec<u8>>,
+}
+
+fn text_content<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.map(String::into_bytes))
+}
+
+thread_local! {
+    static DATA: RefCell<Fixtures> = RefCell::new(Fixtures::default());
+}
+
+/// Backend keeping everything in memory, changes are lost on reload.
+/// Tokens are not checked and any password is accepted for a known user.
+pub struct MemoryClient;
+
+impl MemoryClient {
+    pub fn seed(fixtures: Fixtures) {
+        DATA.with(|d| *d.borrow_mut() = fixtures);
+    }
+
+    fn with<T>(f: impl FnOnce(&mut Fixtures) -> T) -> T {
+        DATA.with(|d| f(&mut d.borrow_mut()))
+    }
+}
+
+fn not_found() -> RequestError {
+    RequestError::Endpoint(404, Error::Status(404, "Not found".to_string()))
+}
+
+/// Creates or updates the blob, keeping its creation date.
+fn put(
+    data: &mut Fixtures,
+    container: &str,
+    filename: &str,
+    fields: serde_json::Map<String, Value>,
+    content: Vec<u8>,
+    now: Value,
+) {
+    let blob = data
+        .blobs
+        .entry(container.to_string())
+        .or_default()
+        .entry(filename.to_string())
+        .or_insert_with(|| FixtureBlob {
+            meta: serde_json::json!({ "created": now.clone() }),
+            content: None,
+        });
+    if let Value::Object(meta) = &mut blob.meta {
+        meta.extend(fields);
+        meta.insert("updated".to_string(), now);
+    }
+    blob.content = Some(content);
+}
+
+fn parse<T: DeserializeOwned>(value: Value) -> Result<T, RequestError> {
+    serde_json::from_value(value).map_err(|e| RequestError::Parse(e.to_string()))
+}
+
+#[yewdux::async_trait(?Send)]
+impl BlobClient for MemoryClient {
+    async fn get_meta<TBlob: TryFrom<BlobMetaData> + DeserializeOwned>(
+        container: &str,
+        filename: &str,
+    ) -> Result<TBlob, RequestError> {
+        let meta = Self::with(|d| {
+            d.blobs
+                .get(container)
+                .and_then(|c| c.get(filename))
+                .map(|b| b.meta.clone())
+        })
+        .ok_or_else(not_found)?;
+        check_visible(&parse::<BlobMetaData>(meta.clone())?)?;
+        parse(meta)
+    }
+    async fn get_meta_all<TBlob: TryFrom<BlobMetaData> + DeserializeOwned>(
+        container: &str,
+        prefix: Option<&str>,
+    ) -> Result<Vec<TBlob>, RequestError> {
+        let metas = Self::with(|d| {
+            d.blobs
+                .get(container)
+                .map(|c| {
+                    c.iter()
+                        .filter(|(name, _)| name.starts_with(prefix.unwrap_or_default()))
+                        .map(|(_, b)| b.meta.clone())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        });
+        let metas = metas
+            .into_iter()
+            .filter_map(|m| parse::<BlobMetaData>(m).ok())
+            .collect();
+        Ok(listed(metas)
+            .into_iter()
+            .filter_map(|b| TBlob::try_from(b).ok())
+            .collect())
+    }
+    async fn get_names(container: &str, prefix: Option<&str>) -> Result<Vec<String>, RequestError> {
+        Ok(Self::with(|d| {
+            d.blobs
+                .get(container)
+                .map(|c| {
+                    c.keys()
+                        .filter(|name| name.starts_with(prefix.unwrap_or_default()))
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default()
+        }))
+    }
+    async fn get_content(container: &str, filename: &str) -> Result<Vec<u8>, RequestError> {
+        let content = Self::with(|d| {
+            d.blobs
+                .get(container)
+                .and_then(|c| c.get(filename))
+                .map(|b| b.content.clone())
+        })
+        .ok_or_else(not_found)?;
+        match content {
+            Some(content) => Ok(content),
+            None => ApiClient::get_content(container, filename).await,
+        }
+    }
+    async fn create_or_update_with_progress(
+        _token: &str,
+        container: &str,
+        upload: &BlobUpload,
+        onprogress: Callback<UploadProgress>,
+    ) -> Result<String, RequestError> {
+        let Value::Object(fields) =
+            serde_json::to_value(&upload.meta).map_err(|e| RequestError::Parse(e.to_string()))?
+        else {
+            return Err(RequestError::Parse("Invalid data format".to_string()));
+        };
+        let now = serde_json::to_value(chrono::Utc::now())
+            .map_err(|e| RequestError::Parse(e.to_string()))?;
+        let filename = upload.meta.filename.clone();
+        Self::with(|d| put(d, container, &filename, fields, upload.content.clone(), now));
+        let size = upload.content.len() as u64;
+        onprogress.emit(UploadProgress {
+            sent: size,
+            total: size,
+        });
+        Ok(filename)
+    }
+    async fn rename(
+        _token: &str,
+        container: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<(), RequestError> {
+        Self::with(|d| {
+            let blobs = d.blobs.get_mut(container).ok_or_else(not_found)?;
+            if blobs.contains_key(to) {
+                return Err(already_exists(to));
+            }
+            let mut blob = blobs.remove(from).ok_or_else(not_found)?;
+            if let Value::Object(meta) = &mut blob.meta {
+                meta.insert("filename".to_string(), Value::String(to.to_string()));
+            }
+            blobs.insert(to.to_string(), blob);
+            Ok(())
+        })
+    }
+    async fn delete(_token: &str, container: &str, filename: &str) -> Result<(), RequestError> {
+        let dir = format!("{}/", filename);
+        Self::with(|d| {
+            let blobs = d.blobs.get_mut(container).ok_or_else(not_found)?;
+            let count = blobs.len();
+            blobs.retain(|name, _| name != filename && !name.starts_with(&dir));
+            match blobs.len() == count {
+                true => Err(not_found()),
+                false => Ok(()),
+            }
+        })
+    }
+}
+
+#[yewdux::async_trait(?Send)]
+impl ResourceClient for MemoryClient {
+    async fn create_resource(
+        token: &str,
+        key: &str,
+        lang: &Country,
+        value: &str,
+    ) -> Result<(), RequestError> {
+        Self::update_resource(token, key, lang, value).await
+    }
+
+    async fn get_resource(key: &str, lang: &Country) -> Result<(Country, String), RequestError> {
+        Self::with(|d| {
+            let values = d.resources.get(key)?;
+            match values.get(lang.key()) {
+                Some(value) => Some((*lang, value.clone())),
+                None => values
+                    .get(Country::UnitedKingdom.key())
+                    .map(|value| (Country::UnitedKingdom, value.clone())),
+            }
+        })
+        .ok_or_else(not_found)
+    }
+
+    async fn get_resource_keys(_token: &str) -> Result<Vec<String>, RequestError> {
+        Ok(Self::with(|d| d.resources.keys().cloned().collect()))
+    }
+
+    async fn update_resource(
+        _token: &str,
+        key: &str,
+        lang: &Country,
+        value: &str,
+    ) -> Result<(), RequestError> {
+        Self::with(|d| {
+            d.resources
+                .entry(key.to_string())
+                .or_default()
+                .insert(lang.key().to_string(), value.to_string());
+        });
+        Ok(())
+    }
+
+    async fn delete_resource(_token: &str, key: &str) -> Result<(), RequestError> {
+        Self::with(|d| d.resources.remove(key))
+            .map(|_| ())
+            .ok_or_else(not_found)
+    }
+
+    async fn delete_resource_lang(
+        _token: &str,
+        key: &str,
+        lang: &Country,
+    ) -> Result<(), RequestError> {
+        Self::with(|d| {
+            d.resources
+                .get_mut(key)
+                .and_then(|values| values.remove(lang.key()))
+        })
+        .map(|_| ())
+        .ok_or_else(not_found)
+    }
+}
+
+#[yewdux::async_trait(?Send)]
+impl UserClient for MemoryClient {
+    async fn login(credentials: Credentials) -> Result<LoginResponse, RequestError> {
+        Self::with(|d| d.users.iter().find(|u| u.name == credentials.name).cloned())
+            .map(|user| LoginResponse {
+                token: "demo".to_string(),
+                user,
+            })
+            .ok_or(RequestError::Endpoint(
+                401,
+                Error::User(UserError::NotFound(credentials.name)),
+            ))
+    }
+
+    async fn register(_credentials: Credentials) -> Result<(), RequestError> {
+        Err(RequestError::Endpoint(
+            403,
+            Error::Status(403, "Registration is disabled in demo mode".to_string()),
+        ))
+    }
+
+    async fn get_users(_token: &str) -> Result<Vec<UserData>, RequestError> {
+        Ok(Self::with(|d| d.users.clone()))
+    }
+
+    async fn activate_user(_token: &str, id: i32) -> Result<(), RequestError> {
+        Self::with(|d| {
+            d.users
+                .iter_mut()
+                .find(|u| u.id == id)
+                .map(|u| u.confirmed = true)
+        })
+        .ok_or_else(not_found)
+    }
+
+    async fn delete_user(_token: &str, id: i32) -> Result<(), RequestError> {
+        Self::with(|d| {
+            let count = d.users.len();
+            d.users.retain(|u| u.id != id);
+            d.users.len() != count
+        })
+        .then_some(())
+        .ok_or_else(not_found)
+    }
+}
+
+#[yewdux::async_trait(?Send)]
+impl SettingsClient for MemoryClient {
+    async fn get_user_settings(
+    ) -> Result<(UsernameRequirements, PasswordRequirements), RequestError> {
+        let settings = Self::with(|d| d.settings.clone()).ok_or_else(not_found)?;
+        parse::<UserSettingsDto>(settings)?
+            .try_into()
+            .map_err(|_: ()| RequestError::Parse("Invalid data format".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use petompp_web_models::models::user::RoleData;
+
+    fn demo() -> Fixtures {
+        serde_yaml::from_str(include_str!("../../fixtures/demo.yml")).unwrap()
+    }
+
+    fn credentials(name: &str) -> Credentials {
+        Credentials {
+            name: name.to_string(),
+            password: "anything".to_string(),
+        }
+    }
+
+    #[test]
+    fn demo_fixtures_have_an_admin() {
+        MemoryClient::seed(demo());
+        let response = block_on(MemoryClient::login(credentials("admin"))).unwrap();
+        assert_eq!(response.user.role, RoleData::Admin);
+    }
+
+    #[test]
+    fn unknown_users_cant_log_in() {
+        MemoryClient::seed(demo());
+        let result = block_on(MemoryClient::login(credentials("nobody")));
+        assert!(matches!(result, Err(RequestError::Endpoint(401, _))));
+    }
+
+    #[test]
+    fn fixture_content_is_read_as_text() {
+        let fixtures: Fixtures = serde_yaml::from_str(
+            "blobs:\n  notes:\n    a.md:\n      meta: {}\n      content: \"# Zażółć\"\n",
+        )
+        .unwrap();
+        MemoryClient::seed(fixtures);
+        let content = block_on(MemoryClient::get_content("notes", "a.md")).unwrap();
+        assert_eq!(content, "# Zażółć".as_bytes());
+    }
+
+    #[test]
+    fn uploads_keep_binary_content() {
+        let png = vec![
+            0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0x00, 0xfe,
+        ];
+        MemoryClient::seed(Fixtures::default());
+        MemoryClient::with(|d| {
+            put(
+                d,
+                "image-upload",
+                "a.png",
+                Default::default(),
+                png.clone(),
+                Value::Null,
+            )
+        });
+        let content = block_on(MemoryClient::get_content("image-upload", "a.png")).unwrap();
+        assert_eq!(content, png);
+    }
+
+    #[test]
+    fn uploads_keep_the_creation_date() {
+        MemoryClient::seed(Fixtures::default());
+        MemoryClient::with(|d| {
+            put(
+                d,
+                "notes",
+                "a.md",
+                Default::default(),
+                vec![],
+                Value::from("first"),
+            );
+            put(
+                d,
+                "notes",
+                "a.md",
+                Default::default(),
+                vec![],
+                Value::from("second"),
+            );
+        });
+        let meta = MemoryClient::with(|d| d.blobs["notes"]["a.md"].meta.clone());
+        assert_eq!(meta["created"], "first");
+        assert_eq!(meta["updated"], "second");
+    }
+
+    #[test]
+    fn rename_and_delete_move_the_blobs() {
+        MemoryClient::seed(Fixtures::default());
+        MemoryClient::with(|d| {
+            put(
+                d,
+                "notes",
+                "dir/a.md",
+                Default::default(),
+                b"a".to_vec(),
+                Value::Null,
+            );
+            put(
+                d,
+                "notes",
+                "dir/b.md",
+                Default::default(),
+                b"b".to_vec(),
+                Value::Null,
+            );
+        });
+        block_on(MemoryClient::rename("", "notes", "dir/a.md", "c.md")).unwrap();
+        assert_eq!(
+            block_on(MemoryClient::get_content("notes", "c.md")).unwrap(),
+            b"a"
+        );
+        block_on(MemoryClient::delete("", "notes", "dir")).unwrap();
+        let names = block_on(MemoryClient::get_names("notes", None)).unwrap();
+        assert_eq!(names, vec!["c.md".to_string()]);
+    }
+
+    #[test]
+    fn rename_keeps_an_existing_target() {
+        MemoryClient::seed(Fixtures::default());
+        MemoryClient::with(|d| {
+            put(
+                d,
+                "notes",
+                "a.md",
+                Default::default(),
+                b"a".to_vec(),
+                Value::Null,
+            );
+            put(
+                d,
+                "notes",
+                "b.md",
+                Default::default(),
+                b"b".to_vec(),
+                Value::Null,
+            );
+        });
+        let result = block_on(MemoryClient::rename("", "notes", "a.md", "b.md"));
+        assert!(matches!(result, Err(RequestError::Endpoint(409, _))));
+        assert_eq!(
+            block_on(MemoryClient::get_content("notes", "b.md")).unwrap(),
+            b"b"
+        );
+        assert!(block_on(MemoryClient::get_content("notes", "a.md")).is_ok());
+    }
+}
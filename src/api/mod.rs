@@ -1,6 +1,17 @@
+pub mod abort;
+pub mod asset;
+pub mod backend;
 pub mod blob;
+pub mod cache;
 pub mod client;
+pub mod container;
 pub mod editor;
+pub mod image;
+pub mod memory;
 pub mod resource;
+pub mod revision;
 pub mod settings;
+pub mod storage;
+pub mod upload;
+pub mod usage;
 pub mod user;
@@ -0,0 +1,110 @@
+use super::{
+    blob::BlobClient,
+    client::RequestError,
+    container::{Container, Revisions},
+    editor::not_found_as_empty,
+};
+use crate::{data::resources::id::ResId, pages::editor::EditorData};
+use chrono::{DateTime, TimeZone, Utc};
+use petompp_web_models::models::{blob::blob_meta::BlobUpload, country::Country};
+use serde::{Deserialize, Serialize};
+use web_sys::{
+    js_sys::{Array, Uint8Array},
+    File, FilePropertyBag,
+};
+
+/// Saved state of a resource, stored as json in the [`Revisions`] container.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Revision {
+    pub created: DateTime<Utc>,
+    pub author: String,
+    pub data: EditorData,
+}
+
+/// What the file name of a revision tells about it, `<millis>_<author>.json`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevisionInfo {
+    pub filename: String,
+    pub created: DateTime<Utc>,
+    pub author: String,
+}
+
+impl RevisionInfo {
+    fn parse(filename: String) -> Option<Self> {
+        let name = filename.rsplit('/').next()?.strip_suffix(".json")?;
+        let (millis, author) = name.split_once('_')?;
+        let created = Utc.timestamp_millis_opt(millis.parse().ok()?).single()?;
+        Some(Self {
+            created,
+            author: author.to_string(),
+            filename,
+        })
+    }
+}
+
+/// Folder with the revisions of a resource in one language.
+pub fn revisions_dir(resid: &ResId, lang: Country) -> String {
+    format!(
+        "{}/{}/",
+        resid.to_string().replacen(':', "/", 1),
+        lang.key()
+    )
+}
+
+#[yewdux::async_trait(?Send)]
+pub trait RevisionClient: BlobClient {
+    /// Revisions of the resource, the newest first.
+    async fn get_revisions(
+        resid: &ResId,
+        lang: Country,
+    ) -> Result<Vec<RevisionInfo>, RequestError> {
+        let names = not_found_as_empty(
+            Self::get_names(Revisions::NAME, Some(&revisions_dir(resid, lang))).await,
+        )?;
+        let mut revisions = names
+            .into_iter()
+            .filter_map(RevisionInfo::parse)
+            .collect::<Vec<_>>();
+        revisions.sort_by(|a, b| b.created.cmp(&a.created));
+        Ok(revisions)
+    }
+    async fn get_revision(info: &RevisionInfo) -> Result<Revision, RequestError> {
+        let content = Self::get_content_str(Revisions::NAME, &info.filename).await?;
+        serde_json::from_str(&content).map_err(|e| RequestError::Parse(e.to_string()))
+    }
+    /// Keeps the saved data, called after every successful save.
+    async fn add_revision(
+        token: &str,
+        resid: &ResId,
+        lang: Country,
+        author: &str,
+        data: &EditorData,
+    ) -> Result<(), RequestError> {
+        let revision = Revision {
+            created: Utc::now(),
+            author: author.replace('/', "-"),
+            data: data.clone(),
+        };
+        let filename = format!(
+            "{}{}_{}.json",
+            revisions_dir(resid, lang),
+            revision.created.timestamp_millis(),
+            revision.author
+        );
+        let json = serde_json::to_vec(&revision).map_err(|e| RequestError::Parse(e.to_string()))?;
+        let mut options = FilePropertyBag::new();
+        options.type_("application/json");
+        let file = File::new_with_u8_array_sequence_and_options(
+            &Array::of1(&Uint8Array::from(json.as_slice())),
+            &filename,
+            &options,
+        )
+        .map_err(|e| RequestError::Parse(format!("{:?}", e)))?;
+        let mut upload = BlobUpload::from_file(&file)
+            .await
+            .map_err(|e| RequestError::Network(e.to_string()))?;
+        upload.meta.filename = filename;
+        Self::create_or_update(token, Revisions::NAME, &upload).await?;
+        Ok(())
+    }
+}
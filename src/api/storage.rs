@@ -0,0 +1,87 @@
+use crate::data::config::{ConfigStore, StorageKind};
+use web_sys::js_sys::{decode_uri_component, encode_uri_component};
+
+/// Public urls of the blobs, content is read from them and they are linked from the markdown.
+pub trait StorageUrl {
+    /// Every blob url starts with it.
+    fn base(&self) -> String;
+
+    /// Segments of the file name are escaped, so names with `#`, `?`, spaces
+    /// or `+` (read as a space by S3) point to the right blob.
+    fn blob_url(&self, container: &str, filename: &str) -> String {
+        format!("{}{}/{}", self.base(), container, encode(filename))
+    }
+
+    /// Container and file name of a blob, if the url points to one.
+    fn split(&self, url: &str) -> Option<(String, String)> {
+        let (container, filename) = url.strip_prefix(self.base().as_str())?.split_once('/')?;
+        Some((container.to_string(), decode(filename)))
+    }
+}
+
+/// Azure public access, `<account url>/<container>/<blob>`.
+pub struct Azure {
+    pub url: String,
+}
+
+impl StorageUrl for Azure {
+    fn base(&self) -> String {
+        self.url.clone()
+    }
+}
+
+/// Path style S3 urls, either with a bucket per container
+/// or with containers being prefixes in a single bucket.
+pub struct S3 {
+    pub url: String,
+    pub bucket: Option<String>,
+}
+
+impl StorageUrl for S3 {
+    fn base(&self) -> String {
+        match &self.bucket {
+            Some(bucket) => format!("{}{}/", self.url, bucket),
+            None => self.url.clone(),
+        }
+    }
+}
+
+/// A folder next to the app, served by Trunk along with it.
+pub struct Local {
+    pub dir: String,
+}
+
+impl StorageUrl for Local {
+    fn base(&self) -> String {
+        format!("/{}/", self.dir.trim_matches('/'))
+    }
+}
+
+/// The storage set in the config.
+pub fn storage() -> Box<dyn StorageUrl> {
+    let config = ConfigStore::get();
+    match config.storage {
+        StorageKind::Azure => Box::new(Azure {
+            url: config.storage_url(),
+        }),
+        StorageKind::S3 { bucket } => Box::new(S3 {
+            url: config.storage_url(),
+            bucket,
+        }),
+        StorageKind::Local { dir } => Box::new(Local { dir }),
+    }
+}
+
+fn encode(filename: &str) -> String {
+    filename
+        .split('/')
+        .map(|s| String::from(encode_uri_component(s)))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn decode(s: &str) -> String {
+    decode_uri_component(s)
+        .map(String::from)
+        .unwrap_or_else(|_| s.to_string())
+}
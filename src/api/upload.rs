@@ -0,0 +1,202 @@
+use super::{
+    abort::AbortScope,
+    backend::Backend,
+    blob::BlobClient,
+    client::{RequestError, UploadProgress},
+    image::{strip_extension, upload_image, upload_name},
+};
+use crate::data::{
+    config::ConfigStore,
+    upload::{UploadQueue, UploadStatus},
+};
+use futures::{channel::oneshot, stream, Future, StreamExt};
+use std::collections::HashSet;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    js_sys::{Array, Function, Promise},
+    DataTransfer, File, FileSystemDirectoryEntry, FileSystemDirectoryReader, FileSystemEntry,
+    FileSystemFileEntry,
+};
+use yew::Callback;
+use yewdux::prelude::Dispatch;
+
+/// What to do with a file named like a blob that already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Collision {
+    Skip,
+    Overwrite,
+    /// Uploads it as `name-1`, `name-2`, ...
+    #[default]
+    Rename,
+}
+
+/// Files dropped with the event, named by their path relative to the dropped folder.
+/// The entries have to be taken while the event is dispatched, so call it from the handler.
+pub fn dropped_files(data: &DataTransfer) -> impl Future<Output = Vec<(String, File)>> {
+    let items = data.items();
+    let entries = (0..items.length())
+        .filter_map(|i| items.get(i))
+        .filter_map(|item| item.webkit_get_as_entry().ok().flatten())
+        .collect::<Vec<_>>();
+    let files = data.files();
+    async move {
+        if entries.is_empty() {
+            let Some(files) = files else {
+                return Vec::new();
+            };
+            return (0..files.length())
+                .filter_map(|i| files.get(i))
+                .map(|f| (f.name(), f))
+                .collect();
+        }
+        let mut found = Vec::new();
+        let mut stack = entries;
+        while let Some(entry) = stack.pop() {
+            if entry.is_directory() {
+                let reader = entry
+                    .unchecked_into::<FileSystemDirectoryEntry>()
+                    .create_reader();
+                // entries come in batches until an empty one
+                while let Ok(batch) = read_entries(&reader).await {
+                    if batch.is_empty() {
+                        break;
+                    }
+                    stack.extend(batch);
+                }
+            } else if let Ok(file) = read_file(entry.unchecked_ref()).await {
+                found.push((entry.full_path().trim_start_matches('/').to_string(), file));
+            }
+        }
+        found.sort_by(|a, b| a.0.cmp(&b.0));
+        found
+    }
+}
+
+async fn read_entries(reader: &FileSystemDirectoryReader) -> Result<Vec<FileSystemEntry>, JsValue> {
+    let entries =
+        promised(|resolve, reject| reader.read_entries_with_callback_and_callback(resolve, reject))
+            .await?;
+    Ok(Array::from(&entries)
+        .iter()
+        .map(|e| e.unchecked_into())
+        .collect())
+}
+
+async fn read_file(entry: &FileSystemFileEntry) -> Result<File, JsValue> {
+    promised(|resolve, reject| {
+        entry.file_with_callback_and_callback(resolve, reject);
+        Ok(())
+    })
+    .await
+    .map(|f| f.unchecked_into())
+}
+
+/// Turns a callback based API into a future.
+async fn promised(
+    call: impl FnOnce(&Function, &Function) -> Result<(), JsValue>,
+) -> Result<JsValue, JsValue> {
+    let mut call = Some(call);
+    let promise = Promise::new(&mut |resolve, reject| {
+        if let Some(call) = call.take() {
+            if let Err(e) = call(&resolve, &reject) {
+                _ = reject.call1(&JsValue::NULL, &e);
+            }
+        }
+    });
+    JsFuture::from(promise).await
+}
+
+/// Uploads the queued files to `dir` in the container, a few at a time.
+pub async fn upload_queued(
+    token: &str,
+    container: &str,
+    dir: &str,
+    ids: Vec<usize>,
+    collision: Collision,
+) -> Result<(), RequestError> {
+    let dispatch = Dispatch::<UploadQueue>::new();
+    let mut existing = match Backend::get_names(container, Some(dir)).await {
+        Ok(names) => names.into_iter().collect::<HashSet<_>>(),
+        Err(RequestError::Endpoint(404, _)) => HashSet::new(),
+        Err(e) => return Err(e),
+    };
+    // names of this upload, which no other file of it may overwrite
+    let mut taken = HashSet::new();
+    let mut jobs = Vec::new();
+    for id in ids {
+        let Some(item) = dispatch.get().get(id).cloned() else {
+            continue;
+        };
+        let name = format!("{}{}", dir, item.name);
+        let target = upload_name(&name, &item.file);
+        // `photo.png` and `photo.jpg` are both uploaded as `photo.webp`
+        let collision = match taken.contains(&target) {
+            true => Collision::Rename,
+            false => collision,
+        };
+        let name = match (existing.contains(&target), collision) {
+            (false, _) | (true, Collision::Overwrite) => name,
+            (true, Collision::Skip) => {
+                dispatch.reduce_mut(|q| q.set_status(id, UploadStatus::Skipped));
+                continue;
+            }
+            (true, Collision::Rename) => (1..)
+                .map(|n| numbered(&name, n))
+                .find(|n| !existing.contains(&upload_name(n, &item.file)))
+                .unwrap(),
+        };
+        // files later in the queue must not take the same name
+        existing.insert(upload_name(&name, &item.file));
+        taken.insert(upload_name(&name, &item.file));
+        jobs.push((id, name, item.file, item.scope));
+    }
+    let parallel = ConfigStore::get().uploads.parallel.max(1);
+    stream::iter(jobs)
+        .for_each_concurrent(parallel, |(id, name, file, scope)| {
+            let dispatch = dispatch.clone();
+            let token = token.to_string();
+            let container = container.to_string();
+            async move {
+                let onprogress = {
+                    let dispatch = dispatch.clone();
+                    Callback::from(move |p: UploadProgress| {
+                        dispatch.reduce_mut(|q| q.set_status(id, UploadStatus::Uploading(p)))
+                    })
+                };
+                onprogress.emit(UploadProgress {
+                    sent: 0,
+                    total: file.size() as u64,
+                });
+                let result = scoped(&scope, async move {
+                    upload_image(&token, &container, &name, &file, onprogress).await
+                })
+                .await;
+                let status = match result {
+                    Some(Ok(name)) => UploadStatus::Done(name),
+                    Some(Err(e)) => UploadStatus::Failed(e.to_string()),
+                    None => UploadStatus::Cancelled,
+                };
+                dispatch.reduce_mut(|q| q.set_status(id, status));
+            }
+        })
+        .await;
+    Ok(())
+}
+
+/// Runs the future in the scope, `None` if the scope was aborted before it finished.
+async fn scoped<T: 'static>(
+    scope: &AbortScope,
+    future: impl Future<Output = T> + 'static,
+) -> Option<T> {
+    let (tx, rx) = oneshot::channel();
+    scope.spawn(async move {
+        _ = tx.send(future.await);
+    });
+    rx.await.ok()
+}
+
+fn numbered(name: &str, n: usize) -> String {
+    let stem = strip_extension(name);
+    format!("{}-{}{}", stem, n, &name[stem.len()..])
+}
@@ -0,0 +1,66 @@
+use super::{image::is_thumbnail, storage::storage};
+use crate::data::resources::id::ResId;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Resources referencing each blob, keyed by container and path.
+/// Paths ending with `/` stand for everything in that folder.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageIndex {
+    used: BTreeMap<(String, String), BTreeSet<ResId>>,
+    /// Resources that couldn't be read, with the error. Blobs only they use look unused.
+    pub skipped: BTreeMap<ResId, String>,
+}
+
+impl UsageIndex {
+    pub fn add(&mut self, container: &str, path: &str, res_id: &ResId) {
+        self.used
+            .entry((container.to_string(), path.to_string()))
+            .or_default()
+            .insert(res_id.clone());
+    }
+
+    /// Adds every blob linked from the markdown.
+    pub fn scan(&mut self, res_id: &ResId, markdown: &str) {
+        let storage = storage();
+        let base = storage.base();
+        for (start, _) in markdown.match_indices(base.as_str()) {
+            let link = &markdown[start..];
+            let end = link
+                .find(|c: char| c.is_whitespace() || "()<>\"'?#".contains(c))
+                .unwrap_or(link.len());
+            if let Some((container, path)) = storage.split(&link[..end]) {
+                self.add(&container, &path, res_id);
+            }
+        }
+    }
+
+    pub fn skip(&mut self, res_id: &ResId, error: impl ToString) {
+        self.skipped.insert(res_id.clone(), error.to_string());
+    }
+
+    /// Resources using the blob, or anything in the folder if `path` ends with `/`.
+    pub fn used_by(&self, container: &str, path: &str) -> Vec<ResId> {
+        let mut found = self
+            .used
+            .iter()
+            .filter(|((c, p), _)| {
+                c == container
+                    && (p == path
+                        || (p.ends_with('/') && path.starts_with(p.as_str()))
+                        || (path.ends_with('/') && p.starts_with(path)))
+            })
+            .flat_map(|(_, ids)| ids.iter().cloned())
+            .collect::<Vec<_>>();
+        found.sort();
+        found.dedup();
+        found
+    }
+
+    /// Blobs from `names` nothing references, thumbnails go along with their images.
+    pub fn orphans(&self, container: &str, names: Vec<String>) -> Vec<String> {
+        names
+            .into_iter()
+            .filter(|name| !is_thumbnail(name) && self.used_by(container, name).is_empty())
+            .collect()
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{api::asset::AssetKind, utils::style::get_svg_bg_mask_style};
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct AssetPreviewProps {
+    pub src: String,
+    #[prop_or_default]
+    pub class: Classes,
+    /// Only used for images, the other kinds come with their own controls.
+    #[prop_or_default]
+    pub onclick: Option<Callback<MouseEvent>>,
+}
+
+/// Image, player or a link to the file, depending on what `src` points to.
+#[function_component(AssetPreview)]
+pub fn asset_preview(props: &AssetPreviewProps) -> Html {
+    let src = props.src.clone();
+    let class = props.class.clone();
+    match AssetKind::from_name(&src) {
+        AssetKind::Image => html! {
+            <img {class} {src} onclick={props.onclick.clone()}/>
+        },
+        AssetKind::Video => html! {
+            <video {class} {src} controls={true} preload={"metadata"}/>
+        },
+        AssetKind::Audio => html! {
+            <audio class={classes!(class, "w-full")} {src} controls={true}/>
+        },
+        kind => {
+            let name = src
+                .split(['?', '#'])
+                .next()
+                .and_then(|s| s.rsplit('/').next())
+                .map(|s| {
+                    web_sys::js_sys::decode_uri_component(s)
+                        .map(String::from)
+                        .unwrap_or(s.to_string())
+                })
+                .unwrap_or_default();
+            html! {
+                <a class={"btn btn-primary flex flex-row flex-nowrap gap-2 m-auto max-w-full"} href={src} target={"_blank"} download={""}>
+                    <div class={"bg-primary-content h-5 w-5 shrink-0"} style={get_svg_bg_mask_style(kind.icon())}/>
+                    <span class={"truncate normal-case"}>{name}</span>
+                </a>
+            }
+        }
+    }
+}
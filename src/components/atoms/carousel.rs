@@ -1,4 +1,7 @@
-use crate::components::atoms::modal::{show_modal_callback, ImageData, ModalData, ModalStore};
+use crate::{
+    components::atoms::modal::{show_modal_callback, ImageData, ModalData, ModalStore},
+    utils::js::fallback_src,
+};
 use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::*;
@@ -6,6 +9,8 @@ use yewdux::prelude::*;
 #[derive(Clone, Debug, PartialEq)]
 pub struct Slide {
     pub src: String,
+    /// Shown instead of `src` in the carousel, the full image opens on click.
+    pub thumbnail: Option<String>,
     pub title: String,
     pub summary: Option<String>,
     pub onclick: Option<Callback<MouseEvent>>,
@@ -20,6 +25,7 @@ impl Default for Slide {
     fn default() -> Self {
         Self {
             src: "".to_string(),
+            thumbnail: None,
             title: "".to_string(),
             summary: None,
             onclick: None,
@@ -61,9 +67,11 @@ pub fn carousel(props: &CarouselProps) -> Html {
                 });
                 show_modal_callback(data, modal_dispatch.clone())
         });
+        let src = s.thumbnail.clone().unwrap_or_else(|| s.src.clone());
+        let onerror = fallback_src(s.src.clone());
         html! {
             <div {id} class={"carousel-item w-full cursor-pointer"} {onclick}>
-                <img class={"w-full object-cover"} src={s.src.clone()}/>
+                <img class={"w-full object-cover"} {src} {onerror}/>
                 <div class={"absolute left-[1rem] bottom-[0.5rem] p-2 pr-4 rounded-lg bg-base-100 bg-opacity-60 text-base-content font-semibold"}>
                     <h1 class={"text-3xl"}>{s.title.clone()}</h1>
                     <p class={"text-lg"}>{s.summary.clone().unwrap_or_default()}</p>
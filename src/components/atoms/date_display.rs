@@ -3,6 +3,7 @@ use crate::{
     utils::style::get_svg_bg_mask_style,
 };
 use chrono::{DateTime, Local, Utc};
+use gloo::timers::callback::Interval;
 use yew::prelude::*;
 use yewdux::prelude::*;
 
@@ -68,3 +69,38 @@ pub fn updated_date_display(props: &DateProps) -> Html {
         </div>
     }
 }
+
+/// Time left until `date`, ticking every second.
+#[function_component(Countdown)]
+pub fn countdown(props: &DateProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let now = use_state(Utc::now);
+    {
+        let now = now.clone();
+        use_effect_with_deps(
+            move |_| {
+                let interval = Interval::new(1000, move || now.set(Utc::now()));
+                move || drop(interval)
+            },
+            (),
+        );
+    }
+    let left = props.date - *now;
+    if left.num_seconds() <= 0 {
+        return html! {};
+    }
+    let secs = left.num_seconds();
+    let left = match secs / 86400 {
+        0 => format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60),
+        days => format!(
+            "{}d {:02}:{:02}:{:02}",
+            days,
+            secs / 3600 % 24,
+            secs / 60 % 60,
+            secs % 60
+        ),
+    };
+    html! {
+        <span class={"font-mono text-sm"}>{format!("{} {}", locales_store.get(TK::PublishesIn), left)}</span>
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{
+    data::locales::{store::LocalesStore, tk::TK},
+    utils::diff::{self, Change},
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DiffMode {
+    /// Unified, whole lines marked with their change.
+    #[default]
+    Lines,
+    /// The new text with the changed words highlighted.
+    Words,
+    SideBySide,
+}
+
+impl DiffMode {
+    const ALL: [Self; 3] = [Self::Lines, Self::Words, Self::SideBySide];
+
+    fn label(&self) -> TK {
+        match self {
+            Self::Lines => TK::Lines,
+            Self::Words => TK::Words,
+            Self::SideBySide => TK::SideBySide,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct DiffViewProps {
+    pub old: String,
+    pub new: String,
+    pub mode: DiffMode,
+}
+
+#[function_component(DiffView)]
+pub fn diff_view(props: &DiffViewProps) -> Html {
+    let class = "bg-base-200 rounded-box p-4 font-mono text-sm max-h-96 overflow-auto";
+    match props.mode {
+        DiffMode::Words => {
+            let spans = diff::words(&props.old, &props.new)
+                .into_iter()
+                .map(|c| match c {
+                    Change::Same(s) => html! { <span>{s.to_string()}</span> },
+                    Change::Added(s) => html! { <ins class={"bg-success text-success-content no-underline"}>{s.to_string()}</ins> },
+                    Change::Removed(s) => html! { <del class={"bg-error text-error-content"}>{s.to_string()}</del> },
+                });
+            html! {
+                <div class={classes!(class, "whitespace-pre-wrap", "break-words")}>{for spans}</div>
+            }
+        }
+        DiffMode::Lines => {
+            let lines = diff::lines(&props.old, &props.new)
+                .into_iter()
+                .map(|c| match c {
+                    Change::Same(s) => html! { <div>{format!("  {}", s)}</div> },
+                    Change::Added(s) => html! { <div class={"bg-success text-success-content"}>{format!("+ {}", s)}</div> },
+                    Change::Removed(s) => html! { <div class={"bg-error text-error-content"}>{format!("- {}", s)}</div> },
+                });
+            html! {
+                <div class={classes!(class, "whitespace-pre")}>{for lines}</div>
+            }
+        }
+        DiffMode::SideBySide => {
+            let cells = side_by_side(&diff::lines(&props.old, &props.new))
+                .into_iter()
+                .flat_map(|(old, new)| {
+                    [
+                        cell(old, "bg-error text-error-content"),
+                        cell(new, "bg-success text-success-content"),
+                    ]
+                });
+            html! {
+                <div class={classes!(class, "grid", "grid-cols-2", "gap-x-4", "whitespace-pre-wrap", "break-words")}>
+                    {for cells}
+                </div>
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct DiffModeSelectProps {
+    pub mode: DiffMode,
+    pub onchange: Callback<DiffMode>,
+}
+
+#[function_component(DiffModeSelect)]
+pub fn diff_mode_select(props: &DiffModeSelectProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let buttons = DiffMode::ALL.into_iter().map(|mode| {
+        let class = match mode == props.mode {
+            true => "btn btn-sm join-item btn-active",
+            false => "btn btn-sm join-item",
+        };
+        let onclick = props.onchange.reform(move |_: MouseEvent| mode);
+        html! {
+            <button type={"button"} {class} {onclick}>{locales_store.get(mode.label())}</button>
+        }
+    });
+    html! {
+        <div class={"join"}>
+            {for buttons}
+        </div>
+    }
+}
+
+/// Unchanged lines on both sides, removed ones paired with the lines added in their place.
+fn side_by_side<'a>(changes: &[Change<'a>]) -> Vec<(Option<Change<'a>>, Option<Change<'a>>)> {
+    let mut rows = Vec::new();
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    let flush = |removed: &mut Vec<Change<'a>>, added: &mut Vec<Change<'a>>, rows: &mut Vec<_>| {
+        for i in 0..removed.len().max(added.len()) {
+            rows.push((removed.get(i).copied(), added.get(i).copied()));
+        }
+        removed.clear();
+        added.clear();
+    };
+    for change in changes {
+        match change {
+            Change::Removed(_) => removed.push(*change),
+            Change::Added(_) => added.push(*change),
+            Change::Same(_) => {
+                flush(&mut removed, &mut added, &mut rows);
+                rows.push((Some(*change), Some(*change)));
+            }
+        }
+    }
+    flush(&mut removed, &mut added, &mut rows);
+    rows
+}
+
+fn cell(change: Option<Change>, changed_class: &'static str) -> Html {
+    match change {
+        Some(Change::Same(s)) => html! { <div class={"min-h-5"}>{s.to_string()}</div> },
+        Some(Change::Added(s) | Change::Removed(s)) => {
+            html! { <div class={classes!("min-h-5", changed_class)}>{s.to_string()}</div> }
+        }
+        None => html! { <div class={"min-h-5"}/> },
+    }
+}
@@ -4,11 +4,11 @@ use crate::data::locales::store::LocalesStore;
 use crate::data::locales::tk::TK;
 use crate::data::resources::id::{ResId, ResourceId};
 use crate::data::session::SessionStore;
+use crate::hooks::abort::use_abort_scope;
 use crate::router::route::Route;
 use petompp_web_models::models::user::RoleData;
 use wasm_bindgen::JsCast;
 use web_sys::Element;
-use yew::platform::spawn_local;
 use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::use_store;
@@ -77,9 +77,10 @@ pub fn editable(props: &EditableProps) -> Html {
     let (_, session_dispatch) = use_store::<SessionStore>();
     let (resid, lang) = (props.resid.clone(), locales_store.curr);
     let markdown = use_state_eq(|| State::Ok(None));
+    let abort = use_abort_scope();
     {
         let markdown = markdown.clone();
-        spawn_local(async move {
+        abort.spawn(async move {
             match &*markdown {
                 State::Ok(Some((r, l, _))) if r == &resid && l == &lang => return,
                 State::Loading | State::Err(_) => return,
@@ -1,6 +1,8 @@
+pub mod asset_preview;
 pub mod carousel;
 pub mod collapse;
 pub mod date_display;
+pub mod diff_view;
 pub mod flag;
 pub mod label;
 pub mod link;
@@ -8,5 +10,7 @@ pub mod loading;
 pub mod logo;
 pub mod markdown;
 pub mod modal;
+pub mod publication_badge;
 pub mod resource_select;
 pub mod text_input;
+pub mod upload_progress;
@@ -8,8 +8,12 @@ use yew_router::prelude::*;
 use yewdux::prelude::*;
 
 use crate::{
+    api::container::{Container, ImageUpload},
     components::{
-        atoms::text_input::{InputType, TextInput},
+        atoms::{
+            diff_view::{DiffMode, DiffModeSelect, DiffView},
+            text_input::{InputType, TextInput},
+        },
         organisms::blob_image_select::BlobImageSelect,
     },
     data::locales::{store::LocalesStore, tk::TK},
@@ -34,6 +38,7 @@ pub enum Buttons {
     Confirm(ModalButton),
     ConfirmCancel(ModalButton, ModalButton),
     RiskyCancel(ModalButton, ModalButton),
+    ConfirmRiskyCancel(ModalButton, ModalButton, ModalButton),
 }
 
 impl Default for Buttons {
@@ -51,6 +56,8 @@ pub enum ModalData {
     Form(FormData),
     Image(ImageData),
     ImageSelector(Buttons),
+    Diff(DiffData),
+    Conflict(ConflictData),
 }
 
 impl Default for ModalData {
@@ -66,6 +73,41 @@ pub struct DialogData {
     pub buttons: Buttons,
 }
 
+/// Dialog showing what is about to change.
+#[derive(PartialEq, Clone, Default)]
+pub struct DiffData {
+    pub title: TK,
+    pub message: TK,
+    pub sections: Vec<DiffSection>,
+    pub buttons: Buttons,
+}
+
+#[derive(PartialEq, Clone, Default)]
+pub struct DiffSection {
+    pub label: TK,
+    pub old: String,
+    pub new: String,
+}
+
+/// Dialog for two edits of the same text, both compared to what they started from.
+#[derive(PartialEq, Clone, Default)]
+pub struct ConflictData {
+    pub title: TK,
+    pub message: TK,
+    pub sections: Vec<MergeSection>,
+    pub conflicts: usize,
+    pub buttons: Buttons,
+}
+
+#[derive(PartialEq, Clone, Default)]
+pub struct MergeSection {
+    pub label: TK,
+    pub base: String,
+    pub mine: String,
+    pub theirs: String,
+    pub merged: String,
+}
+
 #[derive(PartialEq, Clone, Default)]
 pub struct FormData {
     pub title: TK,
@@ -129,6 +171,10 @@ pub fn modal() -> Html {
             html! {<ImageModal src={data.src} title={data.title}/>}
         }
         ModalData::ImageSelector(buttons) => html! {<ImageSelectorModal {buttons}/>},
+        ModalData::Diff(data) => {
+            html! {<DiffModal title={data.title} message={data.message} sections={data.sections} buttons={data.buttons}/>}
+        }
+        ModalData::Conflict(data) => html! {<ConflictModal {data}/>},
     }
 }
 
@@ -155,6 +201,138 @@ fn dialog_modal(props: &DialogModalProps) -> Html {
     }
 }
 
+#[derive(Clone, PartialEq, Properties)]
+struct DiffModalProps {
+    pub title: TK,
+    pub message: TK,
+    pub sections: Vec<DiffSection>,
+    pub buttons: Buttons,
+}
+
+#[function_component(DiffModal)]
+fn diff_modal(props: &DiffModalProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let mode = use_state_eq(DiffMode::default);
+    let onchange = {
+        let mode = mode.clone();
+        Callback::from(move |m| mode.set(m))
+    };
+    let sections = props
+        .sections
+        .iter()
+        .filter(|s| s.old != s.new)
+        .map(|s| {
+            html! {
+                <>
+                <p class={"font-semibold"}>{locales_store.get(s.label.clone())}</p>
+                <DiffView old={s.old.clone()} new={s.new.clone()} mode={*mode}/>
+                </>
+            }
+        })
+        .collect::<Vec<_>>();
+    let changes = match sections.is_empty() {
+        true => html! { <p class={"italic"}>{locales_store.get(TK::NoChanges)}</p> },
+        false => html! {
+            <>
+            <DiffModeSelect mode={*mode} {onchange}/>
+            {for sections}
+            </>
+        },
+    };
+    html! {
+        <dialog id={MODAL_ID} class={"modal z-80"}>
+        <form method={"dialog"} class={"modal-box max-w-5xl"}>
+            <h3 class={"font-bold text-lg"}>{locales_store.get(props.title.clone())}</h3>
+            <p class={"py-4"}>{locales_store.get(props.message.clone())}</p>
+            <div class={"flex flex-col gap-2 pb-4"}>
+                {changes}
+            </div>
+            <div class={"flex flex-row-reverse justify-between"}>
+                {get_buttons(&props.buttons, locales_store)}
+            </div>
+        </form>
+        </dialog>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct ConflictModalProps {
+    pub data: ConflictData,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum MergeView {
+    Mine,
+    Theirs,
+    Merged,
+}
+
+#[function_component(ConflictModal)]
+fn conflict_modal(props: &ConflictModalProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let view = use_state_eq(|| MergeView::Merged);
+    let mode = use_state_eq(DiffMode::default);
+    let onmodechange = {
+        let mode = mode.clone();
+        Callback::from(move |m| mode.set(m))
+    };
+    let views = [
+        (MergeView::Mine, TK::DraftChanges),
+        (MergeView::Theirs, TK::SavedChanges),
+        (MergeView::Merged, TK::MergedChanges),
+    ]
+    .into_iter()
+    .map(|(v, label)| {
+        let class = match v == *view {
+            true => "btn btn-sm join-item btn-active",
+            false => "btn btn-sm join-item",
+        };
+        let view = view.clone();
+        let onclick = Callback::from(move |_| view.set(v));
+        html! {
+            <button type={"button"} {class} {onclick}>{locales_store.get(label)}</button>
+        }
+    });
+    let sections = props.data.sections.iter().filter_map(|s| {
+        let new = match *view {
+            MergeView::Mine => &s.mine,
+            MergeView::Theirs => &s.theirs,
+            MergeView::Merged => &s.merged,
+        };
+        (new != &s.base).then(|| {
+            html! {
+                <>
+                <p class={"font-semibold"}>{locales_store.get(s.label.clone())}</p>
+                <DiffView old={s.base.clone()} new={new.clone()} mode={*mode}/>
+                </>
+            }
+        })
+    });
+    html! {
+        <dialog id={MODAL_ID} class={"modal z-80"}>
+        <form method={"dialog"} class={"modal-box max-w-5xl"}>
+            <h3 class={"font-bold text-lg"}>{locales_store.get(props.data.title.clone())}</h3>
+            <p class={"py-4"}>{locales_store.get(props.data.message.clone())}</p>
+            if props.data.conflicts > 0 {
+                <p class={"pb-4 text-warning"}>
+                    {format!("{}: {}", locales_store.get(TK::MergeConflicts), props.data.conflicts)}
+                </p>
+            }
+            <div class={"flex flex-col gap-2 pb-4"}>
+                <div class={"flex flex-row flex-wrap gap-4"}>
+                    <div class={"join"}>{for views}</div>
+                    <DiffModeSelect mode={*mode} onchange={onmodechange}/>
+                </div>
+                {for sections}
+            </div>
+            <div class={"flex flex-row-reverse justify-between"}>
+                {get_buttons(&props.data.buttons, locales_store)}
+            </div>
+        </form>
+        </dialog>
+    }
+}
+
 pub const MODAL_FIELD_PREFIX: &str = "modal-form-field-";
 
 #[derive(Clone, PartialEq, Properties)]
@@ -214,7 +392,7 @@ fn image_selector_modal(props: &ImageSelectorModalProps) -> Html {
     html! {
         <dialog id={MODAL_ID} class={"modal z-80 items-end pb-6 lg:items-center"}>
             <div class={"modal-box overflow-visible flex flex-col gap-2"}>
-                <BlobImageSelect id={format!("{}src", MODAL_FIELD_PREFIX)} container={"image-upload".to_string()} {ondatachanged} data={(*data).clone()}/>
+                <BlobImageSelect id={format!("{}src", MODAL_FIELD_PREFIX)} container={ImageUpload::NAME.to_string()} {ondatachanged} data={(*data).clone()}/>
                 <div class={"flex flex-row-reverse justify-between"}>
                     {get_buttons(&props.buttons, locales_store)}
                 </div>
@@ -378,6 +556,20 @@ fn get_buttons(buttons: &Buttons, locales_store: Rc<LocalesStore>) -> Html {
                 </>
             }
         }
+        Buttons::ConfirmRiskyCancel(confirm_button, risky_button, cancel_button) => {
+            let confirm_onclick = into_modal_onclick(confirm_button.onclick);
+            let risky_onclick = into_modal_onclick(risky_button.onclick);
+            let cancel_onclick = into_modal_onclick(cancel_button.onclick);
+            html! {
+                <>
+                <button class="btn btn-neutral" onclick={cancel_onclick}>{locales_store.get(cancel_button.text_key)}</button>
+                <div class="flex flex-row gap-2">
+                    <button class="btn btn-error" onclick={risky_onclick}>{locales_store.get(risky_button.text_key)}</button>
+                    <button class="btn btn-primary" onclick={confirm_onclick}>{locales_store.get(confirm_button.text_key)}</button>
+                </div>
+                </>
+            }
+        }
     }
 }
 
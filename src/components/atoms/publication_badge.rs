@@ -0,0 +1,33 @@
+use crate::data::locales::{store::LocalesStore, tk::TK};
+use chrono::{DateTime, Local, Utc};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct PublicationBadgeProps {
+    pub draft: bool,
+    pub publish_at: Option<DateTime<Utc>>,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// Tells admins why the content is hidden, nothing when it is public.
+#[function_component(PublicationBadge)]
+pub fn publication_badge(props: &PublicationBadgeProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let class = props.class.clone();
+    match (props.draft, props.publish_at) {
+        (true, _) => html! {
+            <span class={classes!("badge", "badge-warning", class)}>{locales_store.get(TK::Draft)}</span>
+        },
+        (false, Some(date)) if date > Utc::now() => {
+            let date = DateTime::<Local>::from(date).format("%Y-%m-%d %H:%M");
+            html! {
+                <span class={classes!("badge", "badge-info", class)}>
+                    {format!("{}: {}", locales_store.get(TK::Scheduled), date)}
+                </span>
+            }
+        }
+        _ => html! {},
+    }
+}
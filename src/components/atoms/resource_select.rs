@@ -1,5 +1,5 @@
 use crate::{
-    api::{client::ApiClient, editor::EditorClient},
+    api::{backend::Backend, editor::EditorClient},
     components::{
         atoms::{flag::FlagSelect, loading::Loading},
         state::State,
@@ -7,7 +7,9 @@ use crate::{
     data::{
         locales::{store::LocalesStore, tk::TK},
         resources::{
+            content::{ContentType, MetaKind},
             id::{BlobType, ResId, ResourceId},
+            publication::Publishable,
             store::LocalStore,
         },
         session::SessionStore,
@@ -36,6 +38,7 @@ enum Mode {
     Resources,
     Blogs,
     Projects,
+    Content(&'static ContentType),
 }
 
 impl From<&ResId> for Mode {
@@ -44,16 +47,40 @@ impl From<&ResId> for Mode {
             ResId::ResKey(_) => Self::Resources,
             ResId::Blob(BlobType::Blog(_)) => Self::Blogs,
             ResId::Blob(BlobType::Project(_)) => Self::Projects,
+            ResId::Blob(BlobType::Content(kind, _)) => match ContentType::get(kind) {
+                Some(c) => Self::Content(c),
+                None => Self::Resources,
+            },
         }
     }
 }
 
 impl Mode {
     fn next(&self) -> Self {
+        let enabled = ContentType::enabled();
+        let next_content = |i: usize| match enabled.get(i) {
+            Some(c) => Self::Content(c),
+            None => Self::Resources,
+        };
         match self {
             Self::Resources => Self::Blogs,
             Self::Blogs => Self::Projects,
-            Self::Projects => Self::Resources,
+            Self::Projects => next_content(0),
+            Self::Content(c) => next_content(
+                enabled
+                    .iter()
+                    .position(|e| e.key == c.key)
+                    .map_or(usize::MAX, |i| i + 1),
+            ),
+        }
+    }
+
+    fn contains(&self, resid: &ResId) -> bool {
+        match resid {
+            ResId::Blob(BlobType::Content(kind, _)) => {
+                matches!(self, Self::Content(c) if c.key == kind)
+            }
+            _ => false,
         }
     }
 }
@@ -63,6 +90,8 @@ struct ResourceSelectState {
     resources: (Vec<ResId>, Vec<ResId>),
     blogs: (Vec<ResId>, Vec<ResId>),
     projects: (Vec<ResId>, Vec<ResId>),
+    /// Every enabled content type, filtered by the mode when shown.
+    content: (Vec<ResId>, Vec<ResId>),
 }
 
 #[function_component(ResourceSelect)]
@@ -90,19 +119,21 @@ pub fn resource_select(props: &ResourceSelectProps) -> Html {
             let mut cached_res = Vec::new();
             let mut cached_blogs = Vec::new();
             let mut cached_projects = Vec::new();
+            let mut cached_content = Vec::new();
             for resid in local_store.get_all_resids() {
                 match &resid {
                     ResId::ResKey(_) => cached_res.push(resid),
                     ResId::Blob(bt) => match bt {
                         BlobType::Blog(_) => cached_blogs.push(resid),
                         BlobType::Project(_) => cached_projects.push(resid),
+                        BlobType::Content(_, _) => cached_content.push(resid),
                     },
                 }
             }
             let token = token.clone();
             spawn_local(async move {
-                match ApiClient::get_res_ids(token.as_str()).await {
-                    Ok((res, bl, prj)) => {
+                match Backend::get_res_ids(token.as_str()).await {
+                    Ok((res, bl, prj, content)) => {
                         let cached_res: Vec<_> = cached_res
                             .into_iter()
                             .filter(|r| !res.contains(r))
@@ -115,10 +146,15 @@ pub fn resource_select(props: &ResourceSelectProps) -> Html {
                             .into_iter()
                             .filter(|r| !prj.contains(r))
                             .collect();
+                        let cached_content: Vec<_> = cached_content
+                            .into_iter()
+                            .filter(|r| !content.contains(r))
+                            .collect();
                         state.set(State::Ok(Some(ResourceSelectState {
                             resources: (res, cached_res),
                             blogs: (bl, cached_blogs),
                             projects: (prj, cached_projects),
+                            content: (content, cached_content),
                         })));
                     }
                     Err(e) => state.set(State::Err(e)),
@@ -154,7 +190,7 @@ pub fn resource_select(props: &ResourceSelectProps) -> Html {
             html! {
                 <ResourceList currentresid={props.resid.clone()} currentlang={props.lang}
                     resources={state.resources} blogs={state.blogs} projects={state.projects}
-                    onselectedchanged={onselectedchanged_resid}/>
+                    content={state.content} onselectedchanged={onselectedchanged_resid}/>
             }
         }
         State::Ok(None) | State::Loading => html! {
@@ -196,6 +232,7 @@ struct ResourceListProps {
     pub resources: (Vec<ResId>, Vec<ResId>),
     pub blogs: (Vec<ResId>, Vec<ResId>),
     pub projects: (Vec<ResId>, Vec<ResId>),
+    pub content: (Vec<ResId>, Vec<ResId>),
     pub onselectedchanged: Callback<ResId>,
 }
 
@@ -213,9 +250,14 @@ fn resource_list(props: &ResourceListProps) -> Html {
     let res_page = use_state_eq(|| 0);
     let blog_page = use_state_eq(|| 0);
     let proj_page = use_state_eq(|| 0);
+    let content_page = use_state_eq(|| 0);
     let onclick = {
         let mode = mode.clone();
-        Callback::from(move |_| mode.set(mode.next()))
+        let content_page = content_page.clone();
+        Callback::from(move |_| {
+            content_page.set(0);
+            mode.set(mode.next())
+        })
     };
     let get_btn_onclick = |r: &ResId| {
         let props = props.clone();
@@ -290,6 +332,20 @@ fn resource_list(props: &ResourceListProps) -> Html {
                     .collect::<Vec<_>>(),
             ),
         ),
+        Mode::Content(c) => (
+            locales_store.get(TK::Label(c.title)),
+            content_page,
+            vec_into_elements(
+                props
+                    .content
+                    .0
+                    .iter()
+                    .map(|r| (r, false))
+                    .chain(props.content.1.iter().map(|r| (r, true)))
+                    .filter(|(r, _)| mode.contains(r))
+                    .collect::<Vec<_>>(),
+            ),
+        ),
     };
     let page_count = elements.len();
     let inc_page = {
@@ -327,8 +383,8 @@ fn resource_list(props: &ResourceListProps) -> Html {
                     let onselectedchanged = onselectedchanged.clone();
                     new_element_input.set(State::Loading);
                     spawn_local(async move {
-                        match ApiClient::get_res_ids(token.unwrap_or_default().as_str()).await {
-                            Ok((res, bl, prj)) => {
+                        match Backend::get_res_ids(token.unwrap_or_default().as_str()).await {
+                            Ok((res, bl, prj, content)) => {
                                 let (resid, data, exists) = match *mode {
                                     Mode::Resources => {
                                         let resid = ResId::ResKey(id);
@@ -336,7 +392,8 @@ fn resource_list(props: &ResourceListProps) -> Html {
                                         (resid, EditorData::Resource(Default::default()), contains)
                                     }
                                     Mode::Blogs => {
-                                        let meta = BlogMetaData::empty(&id, currlang);
+                                        let mut meta = BlogMetaData::empty(&id, currlang);
+                                        meta.set_draft(true);
                                         let resid = ResId::Blob(BlobType::Blog(id));
                                         let contains = bl.contains(&resid);
                                         (
@@ -346,7 +403,8 @@ fn resource_list(props: &ResourceListProps) -> Html {
                                         )
                                     }
                                     Mode::Projects => {
-                                        let meta = ProjectMetaData::empty(&id, currlang);
+                                        let mut meta = ProjectMetaData::empty(&id, currlang);
+                                        meta.set_draft(true);
                                         let resid = ResId::Blob(BlobType::Project(id));
                                         let contains = prj.contains(&resid);
                                         (
@@ -355,6 +413,25 @@ fn resource_list(props: &ResourceListProps) -> Html {
                                             contains,
                                         )
                                     }
+                                    Mode::Content(c) => {
+                                        let data = match c.meta {
+                                            MetaKind::Blog => {
+                                                let mut meta = BlogMetaData::empty(&id, currlang);
+                                                meta.set_draft(true);
+                                                EditorData::Blog((Default::default(), meta))
+                                            }
+                                            MetaKind::Project => {
+                                                let mut meta =
+                                                    ProjectMetaData::empty(&id, currlang);
+                                                meta.set_draft(true);
+                                                EditorData::Project((Default::default(), meta))
+                                            }
+                                        };
+                                        let resid =
+                                            ResId::Blob(BlobType::Content(c.key.to_string(), id));
+                                        let contains = content.contains(&resid);
+                                        (resid, data, contains)
+                                    }
                                 };
                                 if !exists {
                                     local_dispatch.reduce_mut(|s| {
@@ -391,6 +468,7 @@ fn resource_list(props: &ResourceListProps) -> Html {
                 Mode::Resources => locales_store.get(TK::NewResource),
                 Mode::Blogs => locales_store.get(TK::NewBlogPost),
                 Mode::Projects => locales_store.get(TK::NewProject),
+                Mode::Content(c) => locales_store.get(TK::Label(c.new_item)),
             }
         }},
         State::Loading => html! {
@@ -10,6 +10,7 @@ use yew::prelude::*;
 pub enum InputType {
     Text,
     Password,
+    DateTimeLocal,
 }
 
 impl InputType {
@@ -17,6 +18,7 @@ impl InputType {
         match self {
             InputType::Text => "text",
             InputType::Password => "password",
+            InputType::DateTimeLocal => "datetime-local",
         }
     }
 }
@@ -0,0 +1,29 @@
+use crate::{
+    api::client::UploadProgress,
+    data::locales::{store::LocalesStore, tk::TK},
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(Clone, Properties, PartialEq)]
+pub struct UploadProgressBarProps {
+    pub progress: UploadProgress,
+    pub oncancel: Callback<MouseEvent>,
+}
+
+#[function_component(UploadProgressBar)]
+pub fn upload_progress_bar(props: &UploadProgressBarProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let text = format!(
+        "{}: {}%",
+        locales_store.get(TK::Uploading),
+        props.progress.percent()
+    );
+    html! {
+        <div class={"flex flex-row gap-2 items-center grow"}>
+            <p class={"text-sm whitespace-nowrap"}>{text}</p>
+            <progress class={"progress progress-primary grow"} value={props.progress.sent.to_string()} max={props.progress.total.max(1).to_string()}/>
+            <button class={"btn btn-xs btn-warning"} onclick={props.oncancel.clone()}>{locales_store.get(TK::Cancel)}</button>
+        </div>
+    }
+}
@@ -1 +1,2 @@
+pub mod upcoming;
 pub mod user_manager;
@@ -0,0 +1,92 @@
+use crate::{
+    api::{backend::Backend, editor::EditorClient},
+    components::{
+        atoms::{date_display::Countdown, link::RouteLink, loading::Loading},
+        state::State,
+    },
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::{content::ContentType, id::BlobType},
+    },
+    hooks::abort::use_abort_scope,
+    router::route::Route,
+};
+use chrono::{DateTime, Local};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+/// Content published ahead of time, with the time left until it shows up.
+#[function_component(Upcoming)]
+pub fn upcoming() -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let data = use_state_eq(|| State::Ok(None));
+    let abort = use_abort_scope();
+    use_effect_with_deps(
+        move |data| {
+            let data = data.clone();
+            match &*data {
+                State::Ok(Some(_)) | State::Loading | State::Err(_) => return,
+                _ => data.set(State::Loading),
+            };
+            abort.spawn(async move {
+                match Backend::get_scheduled().await {
+                    Ok(scheduled) => data.set(State::Ok(Some(scheduled))),
+                    Err(error) => data.set(State::Err(error)),
+                };
+            })
+        },
+        data.clone(),
+    );
+    let list = match &*data {
+        State::Ok(Some(scheduled)) if scheduled.is_empty() => html! {
+            <tr><td colspan={"4"}>{locales_store.get(TK::NoUpcoming)}</td></tr>
+        },
+        State::Ok(Some(scheduled)) => scheduled
+            .iter()
+            .map(|s| {
+                let kind = match &s.id {
+                    BlobType::Blog(_) => locales_store.get(TK::BlogPost),
+                    BlobType::Project(_) => locales_store.get(TK::Project),
+                    BlobType::Content(kind, _) => ContentType::get(kind)
+                        .map(|c| locales_store.get(TK::Label(c.item)))
+                        .unwrap_or(kind.clone()),
+                };
+                let date = DateTime::<Local>::from(s.publish_at).format("%Y-%m-%d %H:%M");
+                html! {
+                    <tr>
+                        <td class={"break-all"}>
+                            <RouteLink route={Route::from(&s.id)} text={s.title.clone()}/>
+                        </td>
+                        <td>{format!("{} ({})", kind, s.lang.key())}</td>
+                        <td>{date.to_string()}</td>
+                        <td><Countdown date={s.publish_at}/></td>
+                    </tr>
+                }
+            })
+            .collect::<Html>(),
+        State::Loading | State::Ok(None) => html! {
+            <tr><td colspan={"4"}><Loading /></td></tr>
+        },
+        State::Err(e) => html! {
+            <tr><td colspan={"4"} class={"text-error"}>{e.to_string()}</td></tr>
+        },
+    };
+    html! {
+        <div class={"flex flex-col lg:w-3/4 w-full mx-auto"}>
+            <p class={"text-2xl font-bold font-mono mb-2"}>{locales_store.get(TK::Upcoming)}</p>
+            <table class={"table"}>
+                <thead>
+                    <tr>
+                        <th>{locales_store.get(TK::Title)}</th>
+                        <th/>
+                        <th>{locales_store.get(TK::PublishAt)}</th>
+                        <th/>
+                    </tr>
+                </thead>
+                <tbody>
+                    {list}
+                </tbody>
+            </table>
+        </div>
+    }
+}
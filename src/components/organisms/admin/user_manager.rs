@@ -1,5 +1,5 @@
 use crate::{
-    api::{client::ApiClient, user::UserClient},
+    api::{backend::Backend, user::UserClient},
     async_event,
     components::{
         atoms::{
@@ -12,9 +12,10 @@ use crate::{
         locales::{store::LocalesStore, tk::TK},
         session::SessionStore,
     },
+    hooks::abort::use_abort_scope,
 };
 use petompp_web_models::models::user::UserData;
-use yew::{platform::spawn_local, prelude::*, virtual_dom::VNode};
+use yew::{prelude::*, virtual_dom::VNode};
 use yewdux::prelude::*;
 
 #[function_component(UserManager)]
@@ -23,15 +24,16 @@ pub fn user_manager() -> Html {
     let (session_store, session_dispatch) = use_store::<SessionStore>();
     let token = session_store.token.clone().unwrap_or_default();
     let data = use_state_eq(|| State::Ok(None));
+    let abort = use_abort_scope();
     use_effect_with_deps(
-        |data| {
+        move |data| {
             let data = data.clone();
             match &*data {
                 State::Ok(Some(_)) | State::Loading | State::Err(_) => return,
                 _ => data.set(State::Loading),
             };
-            spawn_local(async move {
-                match ApiClient::get_users(&token).await {
+            abort.spawn(async move {
+                match Backend::get_users(&token).await {
                     Ok(users) => data.set(State::Ok(Some(users))),
                     Err(error) => data.set(State::Err(error)),
                 };
@@ -115,7 +117,7 @@ fn activate_button(props: &UserRowProps) -> Html {
     let error_state = use_state_eq(|| None);
     let token = session_store.token.clone().unwrap_or_default();
     let onclick = async_event!(|props, token, error_state| {
-        match ApiClient::activate_user(&token, props.user.id).await {
+        match Backend::activate_user(&token, props.user.id).await {
             Ok(()) => props.reload.emit(()),
             Err(error) => error_state.set(Some(error)),
         }
@@ -158,7 +160,7 @@ fn delete_button(props: &UserRowProps) -> Html {
     let error_state = use_state_eq(|| None);
     let token = session_store.token.clone().unwrap_or_default();
     let onclick = async_event!(|props, token, error_state| {
-        match ApiClient::delete_user(&token, props.user.id).await {
+        match Backend::delete_user(&token, props.user.id).await {
             Ok(()) => props.reload.emit(()),
             Err(error) => error_state.set(Some(error)),
         }
@@ -1,28 +1,38 @@
 use crate::{
     api::{
+        abort::AbortScope,
+        asset::AssetKind,
+        backend::Backend,
         blob::BlobClient,
-        client::{ApiClient, RequestError},
+        client::RequestError,
+        container::ContainerInfo,
+        editor::EditorClient,
+        image::{is_thumbnail, thumbnail_path},
+        upload::{dropped_files, upload_queued, Collision},
+        usage::UsageIndex,
     },
     async_event,
     components::{
         atoms::{
+            asset_preview::AssetPreview,
             loading::Loading,
             modal::{show_modal_callback, Buttons, DialogData, ModalButton, ModalData, ModalStore},
+            upload_progress::UploadProgressBar,
         },
         state::State,
     },
     data::{
-        blob::BlobStore,
         locales::{store::LocalesStore, tk::TK},
+        resources::id::ResId,
         session::SessionStore,
+        upload::{UploadQueue, UploadStatus},
     },
-    hooks::event::use_event,
+    hooks::{container::use_container_revision, event::use_event},
     utils::{ext::Mergable, style::get_svg_bg_mask_style},
 };
-use petompp_web_models::models::blob::blob_meta::BlobUpload;
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlElement, HtmlImageElement, HtmlInputElement, Node};
+use web_sys::{File, HtmlElement, HtmlImageElement, HtmlInputElement, HtmlSelectElement, Node};
 use yew::{platform::spawn_local, prelude::*};
 use yewdux::prelude::*;
 
@@ -47,7 +57,7 @@ pub fn blob_image_select(props: &BlobImageSelectProps) -> Html {
         props.id.clone(),
     );
     let force_open = use_state(|| false);
-    let src = <ApiClient as BlobClient>::get_url(
+    let src = <Backend as BlobClient>::get_url(
         &props.container,
         format!(
             "{}{}",
@@ -84,7 +94,7 @@ pub fn blob_image_select(props: &BlobImageSelectProps) -> Html {
     html! {
         <div class={"flex flex-col gap-2"}>
             <div class={"border p-2 rounded-lg shadow-md w-full lg:max-h-[35%]"}>
-                <img {src} class={"h-auto mx-auto"}/>
+                <AssetPreview {src} class={classes!("h-auto", "mx-auto")}/>
             </div>
             <div class={"w-full"}>
                 <div id={(*id).clone()} tabindex={"0"} class={dropdown_class}>
@@ -117,16 +127,25 @@ pub struct BlobBrowserDialogProps {
 #[function_component(BlobBrowserDialog)]
 pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
     let (session_store, session_dispatch) = use_store::<SessionStore>();
-    let (blob_store, blob_dispatch) = use_store::<BlobStore>();
     let (locales_store, _) = use_store::<LocalesStore>();
     let (_, modal_dispatch) = use_store::<ModalStore>();
     let container = props.config.container.clone();
+    let revision = use_container_revision(&container);
     let prefix = props.config.root.clone();
     let curr = use_state(|| "/".to_string());
     let selected = use_state(|| None);
     let props = props.clone();
     let state = use_state(|| State::Ok(None));
+    let (upload_queue, upload_dispatch) = use_store::<UploadQueue>();
+    let collision = use_state(Collision::default);
     let dir_input_active = use_state(|| false);
+    let rename_input_active = use_state(|| false);
+    let dragged = use_state(|| None::<BrowseItem>);
+    let references = use_state(|| None::<TK>);
+    // with the listing revision it was scanned for
+    let usage = use_state(|| None::<(usize, UsageIndex)>);
+    let usage_requested = use_mut_ref(|| None::<usize>);
+    let orphans = use_state(|| State::<Option<Orphans>, RequestError>::Ok(None));
     let onforceopenchanged = props.onforceopenchanged.clone();
     {
         let parentid = props.parentid.clone();
@@ -149,7 +168,29 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
     }
     {
         let state = state.clone();
-        use_effect_with_deps(move |_| state.set(State::Ok(None)), blob_store)
+        use_effect_with_deps(move |_| state.set(State::Ok(None)), revision)
+    }
+    {
+        let usage = usage.clone();
+        let usage_requested = usage_requested.clone();
+        let token = session_store.token.clone().unwrap_or_default();
+        let readonly = props.config.readonly;
+        use_effect_with_deps(
+            move |(selected, revision)| {
+                // scanned once per listing, when the first item is selected
+                if *selected && !readonly && *usage_requested.borrow() != Some(*revision) {
+                    *usage_requested.borrow_mut() = Some(*revision);
+                    let revision = *revision;
+                    spawn_local(async move {
+                        match Backend::get_usage(&token).await {
+                            Ok(index) => usage.set(Some((revision, index))),
+                            Err(_) => *usage_requested.borrow_mut() = None,
+                        }
+                    });
+                }
+            },
+            (selected.is_some(), revision),
+        )
     }
     use_effect_with_deps(
         |(state, container, prefix)| {
@@ -161,9 +202,10 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
                 _ => {
                     spawn_local(async move {
                         let pf = prefix.clone().unwrap_or_default();
-                        match ApiClient::get_names(&container, prefix.as_deref()).await {
+                        match Backend::get_names(&container, prefix.as_deref()).await {
                             Ok(p) => state.set(State::Ok(Some(
                                 p.into_iter()
+                                    .filter(|p| !is_thumbnail(p))
                                     .map(|p| {
                                         format!(
                                             "/{}",
@@ -208,17 +250,91 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
     let onpathchanged = {
         let curr = curr.clone();
         Callback::from(move |path: String| match path.as_str() {
-            ".." => {
-                let path = curr
-                    .trim_end_matches('/')
-                    .rsplit_once('/')
-                    .map(|(r, _)| r.to_string())
-                    .unwrap_or(curr.to_string());
-                curr.set(format!("{}/", path));
-            }
+            ".." => curr.set(parent_dir(&curr)),
             _ => curr.set(format!("{}{}/", &*curr, path)),
         })
     };
+    // moves the item from the current directory to `to`, relative to the root
+    let onmove = {
+        let state = state.clone();
+        let selected = selected.clone();
+        let references = references.clone();
+        let curr = curr.clone();
+        let prefix = prefix.clone();
+        let container = container.clone();
+        let session_store = session_store.clone();
+        Callback::from(move |(item, to): (BrowseItem, String)| {
+            let (BrowseItem::Dir(name) | BrowseItem::File(name)) = &item;
+            let root = prefix.clone().unwrap_or_default();
+            let from = format!("{}{}{}", root, &curr.as_str()[1..], name);
+            let to = format!("{}{}", root, to);
+            if from == to || to.starts_with(&format!("{}/", from)) {
+                return;
+            }
+            let state = state.clone();
+            let references = references.clone();
+            let container = container.clone();
+            let token = session_store.token.clone().unwrap_or_default();
+            selected.set(None);
+            references.set(None);
+            state.set(State::Loading);
+            spawn_local(async move {
+                let (result, path) = match &item {
+                    BrowseItem::Dir(_) => (
+                        Backend::rename_dir(&token, &container, &from, &to).await,
+                        format!("{}/", from),
+                    ),
+                    BrowseItem::File(_) => {
+                        let result = Backend::rename(&token, &container, &from, &to).await;
+                        if result.is_ok() {
+                            // images uploaded before thumbnails existed don't have one
+                            _ = Backend::rename(
+                                &token,
+                                &container,
+                                &thumbnail_path(&from),
+                                &thumbnail_path(&to),
+                            )
+                            .await;
+                        }
+                        (result, from)
+                    }
+                };
+                if let Err(e) = result {
+                    state.set(State::Err(e));
+                    return;
+                }
+                state.set(State::Ok(None));
+                match Backend::find_references(&token, &container, &path).await {
+                    Ok(found) if found.is_empty() => (),
+                    Ok(found) => {
+                        let found = found.iter().map(ToString::to_string).collect::<Vec<_>>();
+                        references.set(Some(TK::StillReferencedBy(found.join(", "))));
+                    }
+                    Err(e) => references.set(Some(TK::ReferencesNotChecked(e.to_string()))),
+                }
+            });
+        })
+    };
+    let ondragstarted = {
+        let dragged = dragged.clone();
+        Callback::from(move |item| dragged.set(Some(item)))
+    };
+    let ondropped = {
+        let curr = curr.clone();
+        let onmove = onmove.clone();
+        Callback::from(move |target: String| {
+            let Some(item) = (*dragged).clone() else {
+                return;
+            };
+            dragged.set(None);
+            let (BrowseItem::Dir(name) | BrowseItem::File(name)) = &item;
+            let dir = match target.as_str() {
+                ".." => parent_dir(&curr),
+                _ => format!("{}{}/", &*curr, target),
+            };
+            onmove.emit((item.clone(), format!("{}{}", &dir[1..], name)));
+        })
+    };
     let paths = match (*state).clone() {
         State::Ok(Some(paths)) => {
             let mut used = Vec::new();
@@ -226,6 +342,7 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
             let selected = selected.clone();
             let onselectedchanged = onselectedchanged.clone();
             let onpathchanged = onpathchanged.clone();
+            let readonly = props.config.readonly;
             let mut items = {
                 let curr = curr.clone();
                 paths
@@ -257,8 +374,11 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
                         <BrowseListItem
                             selected={(*selected).clone()}
                             {item}
+                            {readonly}
                             onselectedchanged={onselectedchanged.clone()}
-                            onpathchanged={onpathchanged.clone()} />
+                            onpathchanged={onpathchanged.clone()}
+                            ondragstarted={ondragstarted.clone()}
+                            ondropped={ondropped.clone()} />
                     }
                 })
                 .collect()
@@ -267,7 +387,7 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
         State::Err(_) => html! {},
     };
     let src = match selected.as_ref() {
-        Some(BrowseItem::File(name)) => <ApiClient as BlobClient>::get_url(
+        Some(BrowseItem::File(name)) => <Backend as BlobClient>::get_url(
             &container,
             format!(
                 "{}{}{}",
@@ -314,12 +434,26 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
     if !*dir_input_active {
         dir_input_class.push("hidden");
     }
+    let mut rename_input_class = input_class.clone();
+    if !*rename_input_active {
+        rename_input_class.push("hidden");
+    }
     let add_dir_onclick = {
         let dir_input_active = dir_input_active.clone();
+        let rename_input_active = rename_input_active.clone();
         Callback::from(move |_| {
+            rename_input_active.set(false);
             dir_input_active.set(!*dir_input_active);
         })
     };
+    let rename_onclick = {
+        let dir_input_active = dir_input_active.clone();
+        let rename_input_active = rename_input_active.clone();
+        Callback::from(move |_| {
+            dir_input_active.set(false);
+            rename_input_active.set(!*rename_input_active);
+        })
+    };
     let dir_id = use_memo(
         |_| web_sys::window().unwrap().crypto().unwrap().random_uuid()[..10].to_string(),
         (),
@@ -339,6 +473,56 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
         },
         (dir_input_active.clone(), dir_id.clone()),
     );
+    let rename_id = use_memo(
+        |_| web_sys::window().unwrap().crypto().unwrap().random_uuid()[..10].to_string(),
+        (),
+    );
+    use_effect_with_deps(
+        |(rename_input_active, rename_id, selected)| {
+            if **rename_input_active {
+                if let (Some(rename_input), Some(BrowseItem::Dir(name) | BrowseItem::File(name))) = (
+                    web_sys::window()
+                        .and_then(|w| w.document())
+                        .and_then(|d| d.get_element_by_id(rename_id.as_str()))
+                        .and_then(|e| e.dyn_into::<HtmlInputElement>().ok()),
+                    selected.as_ref(),
+                ) {
+                    rename_input.set_value(name);
+                    rename_input.focus().unwrap();
+                    rename_input.select();
+                }
+            }
+        },
+        (
+            rename_input_active.clone(),
+            rename_id.clone(),
+            selected.clone(),
+        ),
+    );
+    let rename_onkeydown = {
+        let curr = curr.clone();
+        let selected = selected.clone();
+        let rename_input_active = rename_input_active.clone();
+        let onmove = onmove.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "Enter" => {
+                e.prevent_default();
+                e.stop_propagation();
+                let element = e.target_unchecked_into::<HtmlInputElement>();
+                let name = element.value().trim().to_string();
+                element.set_value("");
+                rename_input_active.set(false);
+                if let (false, Some(item)) = (name.is_empty(), (*selected).clone()) {
+                    onmove.emit((item, format!("{}{}", &curr.as_str()[1..], name)));
+                }
+            }
+            "Escape" => rename_input_active.set(false),
+            "/" | "\\" => {
+                e.prevent_default();
+            }
+            _ => {}
+        })
+    };
     let add_dir_onkeydown = {
         let curr = curr.clone();
         Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
@@ -360,50 +544,85 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
             _ => {}
         })
     };
-    let oninput = {
+    let accept = ContainerInfo::get(&container)
+        .map(|c| c.accept)
+        .unwrap_or_default();
+    let enqueue = {
         let state = state.clone();
         let curr = curr.clone();
         let prefix = prefix.clone();
         let container = container.clone();
-        let blob_dispatch = blob_dispatch.clone();
         let session_store = session_store.clone();
-        Callback::from(move |e: InputEvent| {
+        let collision = collision.clone();
+        let upload_dispatch = upload_dispatch.clone();
+        Callback::from(move |files: Vec<(String, File)>| {
+            let mut ids = Vec::new();
+            upload_dispatch.reduce_mut(|q| {
+                ids = files
+                    .into_iter()
+                    .filter(|(name, file)| {
+                        ContainerInfo::get(&container)
+                            .is_some_and(|c| c.accepts(name, &file.type_()))
+                    })
+                    .map(|(name, file)| q.push(&container, name, file))
+                    .collect();
+            });
+            if ids.is_empty() {
+                return;
+            }
             let state = state.clone();
-            let curr = curr.clone();
-            let prefix = prefix.clone();
             let container = container.clone();
-            let session_store = session_store.clone();
-            let blob_dispatch = blob_dispatch.clone();
-            let element = e.target_unchecked_into::<HtmlInputElement>();
-            let img = element.files().unwrap().get(0).unwrap();
-            state.set(State::Loading);
+            let collision = *collision;
+            let token = session_store.token.clone().unwrap_or_default();
+            let dir = format!(
+                "{}{}",
+                prefix.clone().unwrap_or_default(),
+                &curr.as_str()[1..]
+            );
             spawn_local(async move {
-                let token = session_store.token.clone().unwrap_or_default();
-                let mut upload = match BlobUpload::from_file(&img).await {
-                    Ok(u) => u,
-                    Err(e) => {
-                        state.set(State::Err(RequestError::Network(e.to_string())));
-                        return;
-                    }
-                };
-                upload.meta.filename = format!(
-                    "{}{}{}",
-                    prefix.unwrap_or_default(),
-                    &curr.as_str()[1..],
-                    upload.meta.filename
-                );
-                match ApiClient::create_or_update(token.as_str(), &container, &upload).await {
-                    Ok(_) => {
-                        state.set(State::Ok(None));
-                        blob_dispatch.reduce_mut(|bs| bs.invalidate());
-                    }
-                    Err(e) => {
-                        state.set(State::Err(e));
-                    }
+                if let Err(e) = upload_queued(&token, &container, &dir, ids, collision).await {
+                    state.set(State::Err(e));
                 }
             });
         })
     };
+    let oninput = {
+        let enqueue = enqueue.clone();
+        Callback::from(move |e: InputEvent| {
+            let element = e.target_unchecked_into::<HtmlInputElement>();
+            let files = element
+                .files()
+                .map(|files| {
+                    (0..files.length())
+                        .filter_map(|i| files.get(i))
+                        .map(|f| (f.name(), f))
+                        .collect()
+                })
+                .unwrap_or_default();
+            // lets the same files be picked again
+            element.set_value("");
+            enqueue.emit(files);
+        })
+    };
+    let readonly = props.config.readonly;
+    let ondragover = Callback::from(move |e: DragEvent| {
+        if !readonly {
+            e.prevent_default();
+        }
+    });
+    let ondrop = Callback::from(move |e: DragEvent| {
+        // already handled as a move within the browser
+        if readonly || e.default_prevented() {
+            return;
+        }
+        let Some(data) = e.data_transfer() else {
+            return;
+        };
+        e.prevent_default();
+        let files = dropped_files(&data);
+        let enqueue = enqueue.clone();
+        spawn_local(async move { enqueue.emit(files.await) });
+    });
     let (delete_class, delete_icon) = match &*selected {
         Some(b) => (
             "btn btn-xs btn-error",
@@ -430,42 +649,66 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
                                        container,
                                        prefix,
                                        state,
-                                       go_up,
-                                       blob_dispatch| {
+                                       go_up| {
         let path = match &*selected {
             Some(BrowseItem::Dir(path) | BrowseItem::File(path)) => path,
             _ => return,
         };
         let token = session_store.token.clone().unwrap_or_default();
-        match ApiClient::delete(
-            &token,
-            &container,
-            format!(
-                "{}{}{}",
-                prefix.unwrap_or_default(),
-                &curr.as_str()[1..],
-                path
-            )
-            .as_str(),
-        )
-        .await
-        {
+        let path = format!(
+            "{}{}{}",
+            prefix.unwrap_or_default(),
+            &curr.as_str()[1..],
+            path
+        );
+        match Backend::delete(&token, &container, &path).await {
             Ok(_) => {
+                if let Some(BrowseItem::File(_)) = &*selected {
+                    // images uploaded before thumbnails existed don't have one
+                    _ = Backend::delete(&token, &container, &thumbnail_path(&path)).await;
+                }
                 if go_up {
                     curr.set("/".to_string());
                 }
                 state.set(State::Ok(None));
-                blob_dispatch.reduce_mut(|bs| bs.invalidate());
             }
             Err(e) => state.set(State::Err(e)),
         }
         selected.set(None);
         onforceopenchanged.emit(false);
     });
-    let title_message = match &*selected {
-        Some(BrowseItem::Dir(_)) => Some((TK::DeleteDir, TK::DeleteDirQuestion)),
-        Some(BrowseItem::File(_)) => Some((TK::DeleteFile, TK::DeleteFileQuestion)),
-        None => None,
+    let used_by = match (usage.as_ref(), selected.as_ref()) {
+        (Some((_, usage)), Some(item)) => {
+            let root = format!(
+                "{}{}",
+                prefix.clone().unwrap_or_default(),
+                &curr.as_str()[1..]
+            );
+            let path = match item {
+                BrowseItem::Dir(name) => format!("{}{}/", root, name),
+                BrowseItem::File(name) => format!("{}{}", root, name),
+            };
+            let found = usage.used_by(&container, &path);
+            (!found.is_empty()).then(|| {
+                found
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+        }
+        _ => None,
+    };
+    let title_message = match (&*selected, used_by.clone()) {
+        (Some(BrowseItem::Dir(_)), None) => Some((TK::DeleteDir, TK::DeleteDirQuestion)),
+        (Some(BrowseItem::File(_)), None) => Some((TK::DeleteFile, TK::DeleteFileQuestion)),
+        (Some(BrowseItem::Dir(_)), Some(found)) => {
+            Some((TK::DeleteDir, TK::DeleteUsedQuestion(found)))
+        }
+        (Some(BrowseItem::File(_)), Some(found)) => {
+            Some((TK::DeleteFile, TK::DeleteUsedQuestion(found)))
+        }
+        (None, _) => None,
     };
     let delete_onclick = match title_message {
         Some((title, message)) => enable_force_open.clone().merge(show_modal_callback(
@@ -474,13 +717,136 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
                 message,
                 buttons: Buttons::RiskyCancel(
                     ModalButton::new(TK::Delete, Some(delete_onclick)),
-                    ModalButton::new(TK::Cancel, Some(disable_force_open)),
+                    ModalButton::new(TK::Cancel, Some(disable_force_open.clone())),
                 ),
             }),
-            modal_dispatch,
+            modal_dispatch.clone(),
         )),
         None => Callback::noop(),
     };
+    let orphans_onclick = {
+        let orphans = orphans.clone();
+        let usage = usage.clone();
+        let container = container.clone();
+        let prefix = prefix.clone();
+        let session_store = session_store.clone();
+        Callback::from(move |_| {
+            let orphans = orphans.clone();
+            let usage = usage.clone();
+            let container = container.clone();
+            let prefix = prefix.clone();
+            let token = session_store.token.clone().unwrap_or_default();
+            // the listing's scan is reused when it is done already
+            let scanned = (*usage)
+                .clone()
+                .filter(|(r, _)| *r == revision)
+                .map(|(_, index)| index);
+            orphans.set(State::Loading);
+            spawn_local(async move {
+                let index = async {
+                    match scanned {
+                        Some(index) => Ok(index),
+                        None => {
+                            let index = Backend::get_usage(&token).await?;
+                            usage.set(Some((revision, index.clone())));
+                            Ok(index)
+                        }
+                    }
+                };
+                let (names, index) =
+                    futures::join!(Backend::get_names(&container, prefix.as_deref()), index);
+                let names = match names {
+                    Err(RequestError::Endpoint(404, _)) => Ok(Vec::new()),
+                    names => names,
+                };
+                match (names, index) {
+                    (Ok(names), Ok(index)) => orphans.set(State::Ok(Some(Orphans {
+                        names: index.orphans(&container, names),
+                        skipped: index.skipped,
+                    }))),
+                    (Err(e), _) | (_, Err(e)) => orphans.set(State::Err(e)),
+                }
+            });
+        })
+    };
+    let delete_orphans = async_event!(|orphans, session_store, container, curr, state| {
+        // a blob used only by a resource that couldn't be read would look unused
+        let State::Ok(Some(Orphans { names, skipped })) = &*orphans else {
+            return;
+        };
+        if !skipped.is_empty() {
+            return;
+        }
+        let token = session_store.token.clone().unwrap_or_default();
+        orphans.set(State::Loading);
+        for name in names {
+            if let Err(e) = Backend::delete(&token, &container, name).await {
+                orphans.set(State::Err(e));
+                return;
+            }
+            _ = Backend::delete(&token, &container, &thumbnail_path(name)).await;
+        }
+        orphans.set(State::Ok(None));
+        curr.set("/".to_string());
+        state.set(State::Ok(None));
+    });
+    let orphans_report = match &*orphans {
+        State::Ok(None) => None,
+        State::Ok(Some(Orphans { names, skipped })) => {
+            let pf = prefix.clone().unwrap_or_default();
+            let items = names.iter().map(|name| {
+                html! {<li class={"truncate"}>{format!("/{}", name.strip_prefix(pf.as_str()).unwrap_or(name))}</li>}
+            });
+            let delete_all = (!names.is_empty() && skipped.is_empty()).then(|| {
+                let onclick = enable_force_open.clone().merge(show_modal_callback(
+                    ModalData::Dialog(DialogData {
+                        title: TK::DeleteUnusedFiles,
+                        message: TK::DeleteUnusedFilesQuestion,
+                        buttons: Buttons::RiskyCancel(
+                            ModalButton::new(TK::Delete, Some(delete_orphans)),
+                            ModalButton::new(TK::Cancel, Some(disable_force_open)),
+                        ),
+                    }),
+                    modal_dispatch,
+                ));
+                html! {<button class={"btn btn-xs btn-error"} {onclick}>{locales_store.get(TK::DeleteUnusedFiles)}</button>}
+            });
+            let message = match names.is_empty() {
+                true => TK::NoUnusedFiles,
+                false => TK::UnusedFiles,
+            };
+            let skipped = (!skipped.is_empty()).then(|| {
+                let skipped = skipped
+                    .iter()
+                    .map(|(id, e)| format!("{} ({})", id, e))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                html! {<p class={"text-warning"}>{locales_store.get(TK::NotScanned(skipped))}</p>}
+            });
+            Some(html! {
+                <>
+                <p class={"font-semibold"}>{locales_store.get(message)}</p>
+                {skipped}
+                <ul class={"max-h-24 overflow-y-auto font-mono"}>{for items}</ul>
+                {delete_all}
+                </>
+            })
+        }
+        State::Loading => Some(html! {<Loading />}),
+        State::Err(e) => Some(html! {<p>{e.to_string()}</p>}),
+    }
+    .map(|report| {
+        let orphans = orphans.clone();
+        let onclick = Callback::from(move |_| orphans.set(State::Ok(None)));
+        html! {
+            <div class={"flex flex-row items-start gap-2 border rounded-md bg-base-100 p-2 text-sm"}>
+                <div class={"flex flex-col gap-1 grow min-w-0"}>{report}</div>
+                <button class={"btn btn-xs btn-ghost btn-square"} {onclick}>
+                    <div class={"bg-base-content h-4 w-4"} style={get_svg_bg_mask_style("/img/ui/x.svg")}/>
+                </button>
+            </div>
+        }
+    });
     let buttons = match &*state {
         State::Ok(_) => {
             let add_dir = match props.config.readonly {
@@ -491,14 +857,57 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
                     </button>
                 }),
             };
+            let rename = match (props.config.readonly, selected.is_some()) {
+                (false, true) => Some(html! {
+                    <button class={"btn btn-xs btn-square btn-primary"} title={locales_store.get(TK::Rename)} onclick={rename_onclick}>
+                        <div class={"bg-primary-content h-5 w-5"} style={get_svg_bg_mask_style("/img/ui/rename.svg")}/>
+                    </button>
+                }),
+                _ => None,
+            };
+            let collision_select = match props.config.readonly {
+                true => None,
+                false => {
+                    let collision = collision.clone();
+                    let onchange = Callback::from(move |e: Event| {
+                        let element = e.target_unchecked_into::<HtmlSelectElement>();
+                        collision.set(match element.value().as_str() {
+                            "skip" => Collision::Skip,
+                            "overwrite" => Collision::Overwrite,
+                            _ => Collision::Rename,
+                        });
+                    });
+                    let option = |value: &str, key: TK, c: Collision| {
+                        html! {<option value={value.to_string()} selected={*collision == c}>{locales_store.get(key)}</option>}
+                    };
+                    Some(html! {
+                        <select class={"select select-bordered select-xs"} title={locales_store.get(TK::IfFileExists)} {onchange}>
+                            {option("rename", TK::KeepBoth, Collision::Rename)}
+                            {option("overwrite", TK::Overwrite, Collision::Overwrite)}
+                            {option("skip", TK::Skip, Collision::Skip)}
+                        </select>
+                    })
+                }
+            };
+            let unused = match props.config.readonly {
+                true => None,
+                false => Some(html! {
+                    <button class={"btn btn-xs btn-outline"} onclick={orphans_onclick}>{locales_store.get(TK::UnusedFiles)}</button>
+                }),
+            };
             html! {
                 <>
+                {unused}
                 <button class={delete_class} onclick={delete_onclick}>{delete_icon}</button>
+                {rename}
                 {add_dir}
-                <label class={"btn btn-xs btn-square btn-primary"} onclick={add_img_onclick}>
-                    <div class={"bg-primary-content h-5 w-5"} style={get_svg_bg_mask_style("/img/ui/file-add.svg")}/>
-                    <input {oninput} accept={"image/*"} type={"file"} class={"hidden"} />
-                </label>
+                {collision_select}
+                if !accept.is_empty() {
+                    <label class={"btn btn-xs btn-square btn-primary"} onclick={add_img_onclick}>
+                        <div class={"bg-primary-content h-5 w-5"} style={get_svg_bg_mask_style("/img/ui/file-add.svg")}/>
+                        <input {oninput} {accept} type={"file"} multiple={true} class={"hidden"} />
+                    </label>
+                }
                 </>
             }
         }
@@ -528,6 +937,67 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
             .open_with_url(&element.src())
             .unwrap();
     });
+    let references_alert = (*references).clone().map(|message| {
+        let onclick = Callback::from(move |_| references.set(None));
+        html! {
+            <div class={"alert alert-warning flex flex-row py-1 px-2 text-sm"}>
+                <span class={"grow"}>{locales_store.get(message)}</span>
+                <button class={"btn btn-xs btn-ghost btn-square"} {onclick}>
+                    <div class={"bg-warning-content h-4 w-4"} style={get_svg_bg_mask_style("/img/ui/x.svg")}/>
+                </button>
+            </div>
+        }
+    });
+    let queued = upload_queue.items(&container).collect::<Vec<_>>();
+    let upload_list = (!queued.is_empty()).then(|| {
+        let items = queued.iter().map(|item| {
+            let status = match &item.status {
+                UploadStatus::Queued => html! {<span class={"opacity-60"}>{locales_store.get(TK::Queued)}</span>},
+                UploadStatus::Uploading(progress) => {
+                    let scope = item.scope.clone();
+                    let oncancel = Callback::from(move |_| scope.abort());
+                    html! {<UploadProgressBar progress={*progress} {oncancel}/>}
+                }
+                UploadStatus::Done(name) => html! {<span class={"text-success"} title={name.clone()}>{locales_store.get(TK::Uploaded)}</span>},
+                UploadStatus::Skipped => html! {<span class={"text-warning"}>{locales_store.get(TK::SkippedExists)}</span>},
+                UploadStatus::Cancelled => html! {<span class={"text-warning"}>{locales_store.get(TK::Cancelled)}</span>},
+                UploadStatus::Failed(e) => html! {<span class={"text-error"}>{e}</span>},
+            };
+            html! {
+                <li class={"flex flex-row gap-2 items-center"}>
+                    <span class={"truncate w-1/3"}>{&item.name}</span>
+                    <div class={"flex grow justify-end"}>{status}</div>
+                </li>
+            }
+        });
+        let running = queued.iter().any(|i| !i.status.is_finished());
+        let cancel_all = running.then(|| {
+            let scopes = queued
+                .iter()
+                .filter(|i| !i.status.is_finished())
+                .map(|i| i.scope.clone())
+                .collect::<Vec<_>>();
+            let onclick = Callback::from(move |_| scopes.iter().for_each(AbortScope::abort));
+            html! {<button class={"btn btn-xs btn-warning"} {onclick}>{locales_store.get(TK::CancelAll)}</button>}
+        });
+        let clear = {
+            let container = container.clone();
+            let upload_dispatch = upload_dispatch.clone();
+            let onclick = Callback::from(move |_| {
+                upload_dispatch.reduce_mut(|q| q.clear_finished(&container))
+            });
+            html! {<button class={"btn btn-xs btn-ghost"} {onclick}>{locales_store.get(TK::ClearFinished)}</button>}
+        };
+        html! {
+            <div class={"flex flex-col gap-1 border rounded-md bg-base-100 p-2 text-sm"}>
+                <ul class={"flex flex-col gap-1 max-h-32 overflow-y-auto"}>{for items}</ul>
+                <div class={"flex flex-row gap-2 justify-end"}>
+                    {cancel_all}
+                    {clear}
+                </div>
+            </div>
+        }
+    });
     let ok_cancel_buttons = match props.config.readonly {
         true => None,
         false => Some(html! {
@@ -538,17 +1008,21 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
         }),
     };
     html! {
-        <div class={"bg-base-200 border border-2 rounded-md p-2 shadow-lg"}>
+        <div class={"bg-base-200 border border-2 rounded-md p-2 shadow-lg"} {ondragover} {ondrop}>
             <div class={"lg:max-h-[18rem] flex flex-col"}>
                 <div class={"flex flex-wrap gap-2 p-2 w-full lg:items-center"}>
                     <div class={"flex border rounded-md px-2 italic bg-base-100 grow"}>
                         <p class={"word-break"}>{&*curr}</p>
                         <input id={(*dir_id).clone()} enterkeyhint={"done"} placeholder={locales_store.get(TK::EnterDirname)} class={dir_input_class} onkeydown={add_dir_onkeydown}/>
+                        <input id={(*rename_id).clone()} enterkeyhint={"done"} placeholder={locales_store.get(TK::EnterNewName)} class={rename_input_class} onkeydown={rename_onkeydown}/>
                     </div>
                     <div class={"flex flex-row gap-2 justify-between"}>
                         {buttons}
                     </div>
                 </div>
+                {references_alert}
+                {orphans_report}
+                {upload_list}
                 <div class={"divider my-1 lg:divider-horizontal lg:mx-1 lg:my-auto h-auto"}/>
                 <div class={"flex flex-col lg:flex-row grow p-2 min-h-full"}>
                     <div class={"flex grow flex-col gap-1 overflow-y-scroll overflow-x-hidden lg:min-h-[auto] min-h-[6rem]"}>
@@ -556,7 +1030,10 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
                     </div>
                     <div class={"divider my-1 lg:divider-horizontal lg:mx-1 lg:my-auto h-auto"}/>
                     <div class={"border p-2 rounded-lg shadow-md w-full lg:max-w-[50%] max-h-full"}>
-                        <img class={"m-auto lg:h-full h-24 cursor-pointer"} {src} onclick={img_onclick}/>
+                        <AssetPreview class={classes!("m-auto", "lg:h-full", "h-24", "cursor-pointer")} {src} onclick={img_onclick}/>
+                        if let Some(found) = used_by {
+                            <p class={"text-xs text-center mt-1"}>{locales_store.get(TK::UsedBy(found))}</p>
+                        }
                     </div>
                 </div>
                 {ok_cancel_buttons}
@@ -565,6 +1042,14 @@ pub fn blob_browser_dialog(props: &BlobBrowserDialogProps) -> Html {
     }
 }
 
+/// Result of the unused files check.
+#[derive(Clone, PartialEq)]
+struct Orphans {
+    names: Vec<String>,
+    /// Resources that couldn't be read, with the error.
+    skipped: BTreeMap<ResId, String>,
+}
+
 #[derive(Clone, PartialEq, PartialOrd, Ord, Eq)]
 enum BrowseItem {
     Dir(String),
@@ -575,8 +1060,12 @@ enum BrowseItem {
 struct BrowseListItemProps {
     pub item: BrowseItem,
     pub selected: Option<BrowseItem>,
+    pub readonly: bool,
     pub onselectedchanged: Callback<Option<BrowseItem>>,
     pub onpathchanged: Callback<String>,
+    pub ondragstarted: Callback<BrowseItem>,
+    /// Emits the name of the directory something was dropped on.
+    pub ondropped: Callback<String>,
 }
 
 #[function_component(BrowseListItem)]
@@ -587,6 +1076,14 @@ fn browse_item(props: &BrowseListItemProps) -> Html {
         class.push("btn-active");
     }
     let onselectedchanged = props.onselectedchanged.clone();
+    let draggable =
+        (!props.readonly && props.item != BrowseItem::Dir("..".to_string())).then(|| {
+            let item = props.item.clone();
+            let ondragstarted = props.ondragstarted.clone();
+            Callback::from(move |_: DragEvent| ondragstarted.emit(item.clone()))
+        });
+    let ondragstart = draggable.clone().unwrap_or_default();
+    let draggable = draggable.is_some().to_string();
     match &props.item {
         BrowseItem::Dir(item) => {
             let onclick = {
@@ -601,8 +1098,17 @@ fn browse_item(props: &BrowseListItemProps) -> Html {
                     false => onselectedchanged.emit(Some(props.item.clone())),
                 })
             };
+            let ondragover = Callback::from(|e: DragEvent| e.prevent_default());
+            let ondrop = {
+                let item = item.clone();
+                let ondropped = props.ondropped.clone();
+                Callback::from(move |e: DragEvent| {
+                    e.prevent_default();
+                    ondropped.emit(item.clone());
+                })
+            };
             class.push("btn-accent");
-            html! {<a {onclick} {class}>{item}</a>}
+            html! {<a {onclick} {class} {draggable} {ondragstart} {ondragover} {ondrop}>{item}</a>}
         }
         BrowseItem::File(item) => {
             let onclick = {
@@ -613,7 +1119,22 @@ fn browse_item(props: &BrowseListItemProps) -> Html {
                 }
             };
             class.push("btn-secondary");
-            html! {<a {onclick} {class}>{item}</a>}
+            let icon = AssetKind::from_name(item).icon();
+            html! {
+                <a {onclick} {class} {draggable} {ondragstart}>
+                    <div class={"bg-current h-3 w-3 shrink-0"} style={get_svg_bg_mask_style(icon)}/>
+                    {item}
+                </a>
+            }
         }
     }
 }
+
+fn parent_dir(dir: &str) -> String {
+    let path = dir
+        .trim_end_matches('/')
+        .rsplit_once('/')
+        .map(|(r, _)| r.to_string())
+        .unwrap_or(dir.to_string());
+    format!("{}/", path)
+}
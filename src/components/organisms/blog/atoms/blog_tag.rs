@@ -7,6 +7,9 @@ use crate::router::route::Route;
 #[derive(Clone, PartialEq, Properties)]
 pub struct BlogTagProps {
     pub tag: Tag,
+    /// List filtered by the tag.
+    #[prop_or(Route::Blog)]
+    pub route: Route,
 }
 
 #[function_component(BlogTag)]
@@ -15,6 +18,7 @@ pub fn blog_tag(props: &BlogTagProps) -> Html {
     let location = use_location().unwrap();
     let curr_tags = location.query::<Tags>();
     let tag = props.tag.clone();
+    let route = props.route.clone();
     let (class, next_tags) = match curr_tags {
         Ok(curr_tags) if curr_tags.tags().contains(&tag) => (
             "badge badge-sm badge-primary font-bold",
@@ -47,10 +51,10 @@ pub fn blog_tag(props: &BlogTagProps) -> Html {
         e.set_cancel_bubble(true);
         e.stop_propagation();
         if next_tags.tags().is_empty() {
-            navigator.push(&Route::Blog);
+            navigator.push(&route);
             return;
         }
-        navigator.push_with_query(&Route::Blog, &next_tags).unwrap();
+        navigator.push_with_query(&route, &next_tags).unwrap();
     });
     html! {
         <button {class} {onclick}>{&props.tag.tag}</button>
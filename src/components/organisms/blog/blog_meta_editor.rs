@@ -1,11 +1,15 @@
 use crate::{
+    api::container::{Container, ImageUpload},
     components::{
         atoms::text_input::{InputType, TextInput, TextareaInput},
         organisms::{blob_tags_input::BlobTagsInput, image_link_input::ImageLinkInput},
     },
-    data::locales::{store::LocalesStore, tk::TK},
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::publication::{visible_tags, Publishable},
+    },
 };
-use petompp_web_models::models::blob::blog::BlogMetaData;
+use petompp_web_models::models::{blob::blog::BlogMetaData, tag::Tags};
 use yew::prelude::*;
 use yewdux::prelude::*;
 
@@ -43,7 +47,7 @@ pub fn blog_meta_editor(props: &BlogMetaEditorProps) -> Html {
         let ondatachanged = ondatachanged.clone();
         ondatachanged.reform(move |value| {
             let mut data = data.clone();
-            data.tags = value;
+            data.set_visible_tags(value);
             data
         })
     };
@@ -71,7 +75,7 @@ pub fn blog_meta_editor(props: &BlogMetaEditorProps) -> Html {
                 value={props.data.summary().clone()}
                 onchange={summary_onchange}
                 error={false}/>
-            <BlobTagsInput data={props.data.tags.clone()} ondatachanged={tags_onchange}/>
+            <BlobTagsInput data={Tags::from(visible_tags(&props.data.tags))} ondatachanged={tags_onchange}/>
             <TextInput
                 label={locales_store.get(TK::Created)}
                 itype={InputType::Text}
@@ -82,7 +86,7 @@ pub fn blog_meta_editor(props: &BlogMetaEditorProps) -> Html {
                 itype={InputType::Text}
                 enabled={false}
                 value={props.data.updated.format("%Y-%m-%d %H:%M:%S").to_string()}/>
-            <ImageLinkInput container={"image-upload".to_string()} data={props.data.image().clone()} ondatachanged={image_onchange}/>
+            <ImageLinkInput container={ImageUpload::NAME.to_string()} data={props.data.image().clone()} ondatachanged={image_onchange}/>
         </>
     }
 }
@@ -1,8 +1,16 @@
 use super::atoms::blog_tag::BlogTag;
 use crate::{
-    api::{blob::BlobClient, client::ApiClient},
-    components::atoms::date_display::{CreatedDateDisplay, UpdatedDateDisplay},
+    api::{
+        container::{Container, ImageUpload},
+        image::thumbnail_path,
+    },
+    components::atoms::{
+        date_display::{CreatedDateDisplay, UpdatedDateDisplay},
+        publication_badge::PublicationBadge,
+    },
+    data::resources::publication::{visible_tags, Publishable},
     router::route::Route,
+    utils::js::fallback_src,
 };
 use petompp_web_models::models::blob::blog::BlogMetaData;
 use yew::prelude::*;
@@ -11,29 +19,45 @@ use yew_router::prelude::*;
 #[derive(Clone, Properties, PartialEq)]
 pub struct BlogSummaryProps {
     pub meta: BlogMetaData,
+    /// Key of the content type it belongs to, a blog post if empty.
+    #[prop_or_default]
+    pub kind: Option<String>,
 }
 
 #[function_component(BlogSummary)]
 pub fn blog_summary(props: &BlogSummaryProps) -> Html {
-    let tags = props
-        .meta
-        .tags
-        .tags()
+    let tags = visible_tags(&props.meta.tags)
         .into_iter()
-        .map(|tag| html! { <BlogTag {tag}/> });
+        .map(|tag| match &props.kind {
+            Some(kind) => {
+                html! { <BlogTag {tag} route={Route::ContentList { kind: kind.clone() }}/> }
+            }
+            None => html! { <BlogTag {tag}/> },
+        });
     let style = "-webkit-mask-image: -webkit-linear-gradient(left, rgba(0,0,0,0),rgba(0,0,0,0.8));";
-    let img = match props.meta.image().as_str() {
-        "" => "/img/placeholder.svg".to_string(),
-        img => <ApiClient as BlobClient>::get_url("image-upload", img),
+    let (img, onerror) = match props.meta.image().as_str() {
+        "" => ("/img/placeholder.svg".to_string(), None),
+        img => (
+            ImageUpload::url(&thumbnail_path(img)),
+            Some(fallback_src(ImageUpload::url(img))),
+        ),
     };
     let id = props.meta.id().to_string();
+    let to = match &props.kind {
+        Some(kind) => Route::ContentItem {
+            kind: kind.clone(),
+            id,
+        },
+        None => Route::BlogPost { id },
+    };
 
     html! {
-        <Link<Route> classes={"card card-side bg-base-200 cursor-pointer"} to={Route::BlogPost { id: id.to_string() }}>
+        <Link<Route> classes={"card card-side bg-base-200 cursor-pointer"} {to}>
             <div class={"card-body pt-4 z-10"}>
                 <div class={"flex flex-col gap-4 lg:gap-2"}>
                     <div class={"flex flex-row justify-start"}>
                         <div class={"flex flex-row gap-2 flex-wrap"}>
+                        <PublicationBadge class={"badge-sm"} draft={props.meta.is_draft()} publish_at={props.meta.publish_at()}/>
                         {for tags}
                         </div>
                     </div>
@@ -49,7 +73,7 @@ pub fn blog_summary(props: &BlogSummaryProps) -> Html {
                     </div>
                 </div>
             <figure class={"absolute z-0 right-0 h-full w-2/3 object-fill pointer-events-none"} {style}>
-                <img class={"rounded-xl min-h-full"} src={img}/>
+                <img class={"rounded-xl min-h-full"} src={img} {onerror}/>
             </figure>
         </Link<Route>>
     }
@@ -0,0 +1,67 @@
+use crate::{
+    components::atoms::modal::{
+        show_modal_callback, Buttons, DialogData, ModalButton, ModalData, ModalStore,
+    },
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        persist::Recovery,
+    },
+    utils::js::download,
+};
+use chrono::{DateTime, Local};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+/// Offers the saved data that couldn't be migrated after an update for download,
+/// until it is discarded.
+#[function_component(DataRecovery)]
+pub fn data_recovery() -> Html {
+    let (recovery, recovery_dispatch) = use_store::<Recovery>();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (_, modal_dispatch) = use_store::<ModalStore>();
+    if recovery.items.is_empty() {
+        return html! {};
+    }
+    let items = recovery.items.iter().map(|item| {
+        let ondownload = {
+            let item = item.clone();
+            Callback::from(move |_| {
+                let date = DateTime::<Local>::from(item.data.failed).format("%Y-%m-%d-%H%M");
+                let name = format!("{}-{}.json", item.name().replace('/', "-"), date);
+                download(&name, &item.data.raw, "application/json")
+            })
+        };
+        let ondiscard = {
+            let key = item.failed_key.clone();
+            let recovery_dispatch = recovery_dispatch.clone();
+            Callback::from(move |_| recovery_dispatch.reduce_mut(|r| r.discard(&key)))
+        };
+        let ondiscard = show_modal_callback(
+            ModalData::Dialog(DialogData {
+                title: TK::Discard,
+                message: TK::DiscardRecoveredQuestion,
+                buttons: Buttons::RiskyCancel(
+                    ModalButton::new(TK::Discard, Some(ondiscard)),
+                    ModalButton::new(TK::Cancel, None),
+                ),
+            }),
+            modal_dispatch.clone(),
+        );
+        html! {
+            <li class={"flex flex-row flex-wrap gap-2 items-center"}>
+                <span class={"font-mono break-all"}>{item.name()}</span>
+                <span class={"opacity-70 break-all grow"}>{&item.data.error}</span>
+                <button class={"btn btn-xs"} onclick={ondownload}>{locales_store.get(TK::Download)}</button>
+                <button class={"btn btn-xs btn-warning"} onclick={ondiscard}>{locales_store.get(TK::Discard)}</button>
+            </li>
+        }
+    });
+    html! {
+        <div class={"fixed bottom-4 left-1/2 -translate-x-1/2 z-40 w-11/12 lg:w-1/2 alert alert-warning flex flex-col items-start text-sm"}>
+            <p class={"font-semibold"}>{locales_store.get(TK::DataNotMigrated)}</p>
+            <ul class={"flex flex-col gap-1 w-full"}>
+                {for items}
+            </ul>
+        </div>
+    }
+}
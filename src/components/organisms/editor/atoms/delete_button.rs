@@ -1,7 +1,9 @@
 use crate::{
     api::{
+        backend::Backend,
         blob::BlobClient,
-        client::{ApiClient, RequestError},
+        client::RequestError,
+        container::{Blog, Container, Project},
         resource::ResourceClient,
     },
     async_event,
@@ -48,12 +50,16 @@ pub fn delete_button(props: &EditorProps) -> Html {
         onstatechange.emit(EditorState::Loading);
         match match resid {
             ResId::Blob(blob) => match blob {
-                BlobType::Blog(id) => ApiClient::delete(&token, "blog", &id).await,
-                BlobType::Project(id) => ApiClient::delete(&token, "project", &id).await,
+                BlobType::Blog(id) => Backend::delete(&token, Blog::NAME, &id).await,
+                BlobType::Project(id) => Backend::delete(&token, Project::NAME, &id).await,
+                BlobType::Content(_, ref id) => match blob.container() {
+                    Ok(container) => Backend::delete(&token, container, id).await,
+                    Err(e) => Err(e),
+                },
             },
             ResId::ResKey(id) => match lang {
-                Country::UnitedKingdom => ApiClient::delete_resource(&token, &id).await,
-                _ => ApiClient::delete_resource_lang(&token, &id, &lang).await,
+                Country::UnitedKingdom => Backend::delete_resource(&token, &id).await,
+                _ => Backend::delete_resource_lang(&token, &id, &lang).await,
             },
         } {
             Ok(_) => navigator.push(&Route::Editor),
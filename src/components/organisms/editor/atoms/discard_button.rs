@@ -1,7 +1,8 @@
 use crate::{
     async_event,
-    components::atoms::modal::{
-        show_modal_callback, Buttons, DialogData, ModalButton, ModalData, ModalStore,
+    components::{
+        atoms::modal::{Buttons, ModalButton, ModalStore},
+        organisms::editor::changes::show_changes_callback,
     },
     data::{
         locales::{store::LocalesStore, tk::TK},
@@ -29,9 +30,9 @@ pub fn discard_button(props: &EditorProps) -> Html {
             return html! {};
         }
     };
-    if local_store.get(resid, lang.key()).is_none() {
+    let Some(data) = local_store.get(resid, lang.key()) else {
         return html! {};
-    }
+    };
     let onstatechanged = &props.onstatechanged;
     let onclick = async_event!(
         |onstatechanged, resid, lang, local_dispatch, is_new, navigator| {
@@ -44,15 +45,16 @@ pub fn discard_button(props: &EditorProps) -> Html {
             onstatechanged.emit(EditorState::Ok(None));
         }
     );
-    let onclick = show_modal_callback(
-        ModalData::Dialog(DialogData {
-            title: TK::DiscardChanges,
-            message: TK::DiscardChangesQuestion,
-            buttons: Buttons::RiskyCancel(
-                ModalButton::new(TK::Discard, Some(onclick)),
-                ModalButton::new(TK::Cancel, None),
-            ),
-        }),
+    let onclick = show_changes_callback(
+        TK::DiscardChanges,
+        TK::DiscardChangesQuestion,
+        Buttons::RiskyCancel(
+            ModalButton::new(TK::Discard, Some(onclick)),
+            ModalButton::new(TK::Cancel, None),
+        ),
+        resid.clone(),
+        *lang,
+        data,
         modal_dispatch.clone(),
     );
 
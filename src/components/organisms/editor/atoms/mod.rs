@@ -1,3 +1,4 @@
 pub mod delete_button;
 pub mod discard_button;
+pub mod publish_button;
 pub mod save_button;
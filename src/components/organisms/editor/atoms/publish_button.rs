@@ -0,0 +1,140 @@
+use crate::{
+    api::{backend::Backend, editor::EditorClient},
+    async_event,
+    components::{
+        atoms::modal::{
+            show_modal_callback, Buttons, DialogData, ModalButton, ModalData, ModalStore,
+        },
+        organisms::editor::{atoms::save_button::keep_revision, conflict::save_checked},
+    },
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::{publication::Publishable, store::LocalStore},
+        session::SessionStore,
+    },
+    pages::editor::{EditorData, EditorProps, EditorState},
+    utils::date::{from_input_value, to_input_value},
+};
+use chrono::{DateTime, Utc};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+/// Saves the post or project with the opposite status, keeping any local changes as a draft.
+/// Drafts get published at the date picked next to it, if there is one.
+#[function_component(PublishButton)]
+pub fn publish_button(props: &EditorProps) -> Html {
+    let (session_store, _) = use_store::<SessionStore>();
+    let (local_store, local_dispatch) = use_store::<LocalStore>();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (_, modal_dispatch) = use_store::<ModalStore>();
+    // the publish date picked here, until then the saved one
+    let picked = use_state_eq(|| None::<Option<DateTime<Utc>>>);
+    {
+        let picked = picked.clone();
+        use_effect_with_deps(move |_| picked.set(None), (props.resid.clone(), props.lang));
+    }
+    let state = match &props.state {
+        EditorState::Ok(Some(state)) if !state.is_new.unwrap_or_default() => state,
+        _ => {
+            return html! {};
+        }
+    };
+    let (resid, lang) = &state.id;
+    // the status of the saved version, drafts may have an older one
+    let saved = local_store
+        .base(resid, lang.key())
+        .cloned()
+        .unwrap_or(state.data.clone());
+    let (is_draft, publish_at) = match &saved {
+        EditorData::Blog((_, meta)) => (meta.is_draft(), meta.publish_at()),
+        EditorData::Project((_, meta)) => (meta.is_draft(), meta.publish_at()),
+        EditorData::Resource(_) => {
+            return html! {};
+        }
+    };
+    let onstatechange = props.onstatechanged.clone();
+    let token = session_store.token.clone().unwrap_or_default();
+    let resid = resid.clone();
+    let lang = *lang;
+    let author = session_store
+        .user
+        .as_ref()
+        .map(|u| u.name.clone())
+        .unwrap_or_default();
+    let publish_at = (*picked).unwrap_or(publish_at);
+    let onclick = async_event!(
+        |onstatechange, resid, lang, local_dispatch, token, author| {
+            onstatechange.emit(EditorState::Loading);
+            match Backend::set_status(&token, &resid, lang, !is_draft, publish_at).await {
+                Ok(data) => {
+                    keep_revision(&token, &author, &resid, lang, &data).await;
+                    local_dispatch
+                        .reduce_mut(|store| store.set_published(&resid, lang.key(), data));
+                    onstatechange.emit(EditorState::Ok(None));
+                }
+                Err(e) => {
+                    onstatechange.emit(EditorState::Err(e));
+                }
+            }
+        }
+    );
+    let (text, message) = match (is_draft, publish_at) {
+        (true, Some(date)) if date > Utc::now() => (TK::Schedule, TK::ScheduleQuestion),
+        (true, _) => (TK::Publish, TK::PublishQuestion),
+        (false, _) => (TK::Unpublish, TK::UnpublishQuestion),
+    };
+    // the status is flipped on the saved version, so it has to be the one shown here
+    let onclick = {
+        let (resid, loaded) = (resid.clone(), saved.clone());
+        let onreload = props.onstatechanged.reform(|_| EditorState::Ok(None));
+        let (local_dispatch, modal_dispatch) = (local_dispatch.clone(), modal_dispatch.clone());
+        Callback::from(move |_| {
+            save_checked(
+                resid.clone(),
+                lang,
+                Some(loaded.clone()),
+                onclick.clone(),
+                onreload.clone(),
+                local_dispatch.clone(),
+                modal_dispatch.clone(),
+            )
+        })
+    };
+    let onclick = show_modal_callback(
+        ModalData::Dialog(DialogData {
+            title: text.clone(),
+            message,
+            buttons: Buttons::ConfirmCancel(
+                ModalButton::new(text.clone(), Some(onclick)),
+                ModalButton::new(TK::Cancel, None),
+            ),
+        }),
+        modal_dispatch.clone(),
+    );
+    let class = match is_draft {
+        true => "btn btn-info grow",
+        false => "btn btn-outline btn-info grow",
+    };
+
+    let onchange = Callback::from(move |e: Event| {
+        let input: HtmlInputElement = e.target_unchecked_into();
+        picked.set(Some(from_input_value(&input.value())));
+    });
+
+    html! {
+        <div class={"join grow"}>
+            if is_draft {
+                <input
+                    class={"input input-bordered join-item"}
+                    type={"datetime-local"}
+                    title={locales_store.get(TK::PublishAt)}
+                    value={publish_at.map(to_input_value).unwrap_or_default()}
+                    {onchange}/>
+            }
+            <button class={classes!(class, "join-item")} {onclick}>
+                {locales_store.get(text)}
+            </button>
+        </div>
+    }
+}
@@ -1,17 +1,18 @@
 use crate::{
-    api::{blob::BlobClient, client::ApiClient, resource::ResourceClient},
+    api::{backend::Backend, client::RequestError, editor::EditorClient, revision::RevisionClient},
     async_event,
-    components::atoms::modal::{
-        show_modal_callback, Buttons, DialogData, ModalButton, ModalData, ModalStore,
+    components::{
+        atoms::modal::{show_error, Buttons, ModalButton, ModalStore},
+        organisms::editor::{changes::show_changes_callback, conflict::save_checked},
     },
     data::{
         locales::{store::LocalesStore, tk::TK},
-        resources::store::LocalStore,
+        resources::{id::ResId, store::LocalStore},
         session::SessionStore,
     },
     pages::editor::{EditorData, EditorProps, EditorState},
 };
-use petompp_web_models::models::blob::blob_meta::{BlobMetaDto, BlobUpload};
+use petompp_web_models::models::country::Country;
 use yew::prelude::*;
 use yewdux::prelude::*;
 
@@ -34,55 +35,50 @@ pub fn save_button(props: &EditorProps) -> Html {
     let onstatechange = props.onstatechanged.clone();
     let token = session_store.token.clone().unwrap_or_default();
     let is_new = state.is_new.unwrap_or_default();
-    let onclick = async_event!(|onstatechange, resid, lang, local_dispatch, data, token| {
-        onstatechange.emit(EditorState::Loading);
-        match match data {
-            EditorData::Resource(value) => match is_new {
-                true => ApiClient::create_resource(&token, resid.id(), &lang, &value).await,
-                false => ApiClient::update_resource(&token, resid.id(), &lang, &value).await,
-            },
-            EditorData::Blog((value, meta)) => {
-                let upload = BlobUpload {
-                    meta: BlobMetaDto::from((**meta).clone()),
-                    content: value.into_bytes(),
-                };
-                gloo::console::log!(format!("{:?}", &upload.content));
-                ApiClient::create_or_update(&token, "blog", &upload)
-                    .await
-                    .map(|_| ())
-            }
-            EditorData::Project((value, meta)) => {
-                let upload = BlobUpload {
-                    meta: BlobMetaDto::from((**meta).clone()),
-                    content: value.into_bytes(),
-                };
-                ApiClient::create_or_update(&token, "project", &upload)
-                    .await
-                    .map(|_| ())
-            }
-        } {
-            Ok(_) => {
-                local_dispatch.reduce_mut(|store| store.remove(&resid, lang.key()));
-                onstatechange.emit(EditorState::Ok(None));
-            }
-            Err(e) => {
-                onstatechange.emit(EditorState::Err(e));
+    let author = session_store
+        .user
+        .as_ref()
+        .map(|u| u.name.clone())
+        .unwrap_or_default();
+    let onsave = async_event!(
+        |onstatechange, resid, lang, local_dispatch, data, token, author| {
+            onstatechange.emit(EditorState::Loading);
+            match save_draft(&token, &author, &resid, lang, &data, local_dispatch).await {
+                Ok(_) => onstatechange.emit(EditorState::Ok(None)),
+                Err(e) => onstatechange.emit(EditorState::Err(e)),
             }
         }
-    });
+    );
     let (text, title, message) = match is_new {
         true => (TK::Create, TK::CreateResource, TK::CreateResourceQuestion),
         false => (TK::Save, TK::SaveChanges, TK::SaveChangesQuestion),
     };
-    let onclick = show_modal_callback(
-        ModalData::Dialog(DialogData {
-            title,
-            message,
-            buttons: Buttons::ConfirmCancel(
-                ModalButton::new(TK::Save, Some(onclick)),
-                ModalButton::new(TK::Cancel, None),
-            ),
-        }),
+    let onclick = {
+        let (resid, lang) = (resid.clone(), *lang);
+        let local_dispatch = local_dispatch.clone();
+        let modal_dispatch = modal_dispatch.clone();
+        Callback::from(move |_| {
+            save_checked(
+                resid.clone(),
+                lang,
+                None,
+                onsave.clone(),
+                Callback::noop(),
+                local_dispatch.clone(),
+                modal_dispatch.clone(),
+            )
+        })
+    };
+    let onclick = show_changes_callback(
+        title,
+        message,
+        Buttons::ConfirmCancel(
+            ModalButton::new(TK::Save, Some(onclick)),
+            ModalButton::new(TK::Cancel, None),
+        ),
+        resid.clone(),
+        *lang,
+        data,
         modal_dispatch.clone(),
     );
 
@@ -92,3 +88,32 @@ pub fn save_button(props: &EditorProps) -> Html {
         </button>
     }
 }
+
+/// Saves the draft, keeps it as a revision and drops it from the local store.
+pub async fn save_draft(
+    token: &str,
+    author: &str,
+    resid: &ResId,
+    lang: Country,
+    data: &EditorData,
+    local_dispatch: Dispatch<LocalStore>,
+) -> Result<(), RequestError> {
+    Backend::save_data(token, resid, lang, data).await?;
+    keep_revision(token, author, resid, lang, data).await;
+    local_dispatch.reduce_mut(|store| store.remove(resid, lang.key()));
+    Ok(())
+}
+
+/// Stores the saved data as a revision, telling when it couldn't be, as the save itself succeeded.
+pub async fn keep_revision(
+    token: &str,
+    author: &str,
+    resid: &ResId,
+    lang: Country,
+    data: &EditorData,
+) {
+    if let Err(e) = Backend::add_revision(token, resid, lang, author, data).await {
+        let locales_store = Dispatch::<LocalesStore>::new().get();
+        show_error(locales_store.get(TK::RevisionNotKept(e.to_string())), None);
+    }
+}
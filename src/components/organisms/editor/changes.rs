@@ -0,0 +1,116 @@
+use crate::{
+    api::{backend::Backend, editor::EditorClient},
+    components::atoms::modal::{
+        show_modal, Buttons, DialogData, DiffData, DiffSection, ModalData, ModalStore,
+    },
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::{id::ResId, publication::visible_tags},
+    },
+    pages::editor::EditorData,
+};
+use petompp_web_models::models::{country::Country, tag::Tags};
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+/// Shows what is saved against the local draft before acting on it.
+/// Falls back to a plain dialog when the saved version can't be fetched.
+pub fn show_changes_callback(
+    title: TK,
+    message: TK,
+    buttons: Buttons,
+    resid: ResId,
+    lang: Country,
+    local: EditorData,
+    dispatch: Dispatch<ModalStore>,
+) -> Callback<MouseEvent> {
+    Callback::from(move |_| {
+        let (title, message, buttons) = (title.clone(), message.clone(), buttons.clone());
+        let (resid, local, dispatch) = (resid.clone(), local.clone(), dispatch.clone());
+        spawn_local(async move {
+            let data = match Backend::get_saved(&resid, lang).await {
+                Ok(saved) => ModalData::Diff(DiffData {
+                    title,
+                    message,
+                    sections: changes(saved.as_ref(), &local),
+                    buttons,
+                }),
+                Err(e) => {
+                    gloo::console::error!(format!("failed to get the saved version: {}", e));
+                    ModalData::Dialog(DialogData {
+                        title,
+                        message,
+                        buttons,
+                    })
+                }
+            };
+            show_modal(data, dispatch);
+        });
+    })
+}
+
+/// The metadata and content of `local` against `saved`, as sections of a diff dialog.
+pub fn changes(saved: Option<&EditorData>, local: &EditorData) -> Vec<DiffSection> {
+    let locales_store = Dispatch::<LocalesStore>::new().get();
+    let saved = saved.map(|s| texts(s, &locales_store));
+    texts(local, &locales_store)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (label, new))| DiffSection {
+            old: saved
+                .as_ref()
+                .and_then(|s| s.get(i))
+                .map(|(_, old)| old.clone())
+                .unwrap_or_default(),
+            label,
+            new,
+        })
+        .collect()
+}
+
+/// The metadata, if there is any, and the content as labelled texts to compare.
+pub fn texts(data: &EditorData, locales_store: &LocalesStore) -> Vec<(TK, String)> {
+    let label = match data {
+        EditorData::Blog(_) => Some(TK::BlogPostMetadata),
+        EditorData::Project(_) => Some(TK::ProjectMetadata),
+        EditorData::Resource(_) => None,
+    };
+    label
+        .map(|label| (label, meta_text(data, locales_store)))
+        .into_iter()
+        .chain([(TK::PageContents, data.to_string())])
+        .collect()
+}
+
+/// Metadata as `field: value` lines, so it can be compared like the content.
+fn meta_text(data: &EditorData, locales_store: &LocalesStore) -> String {
+    // the status isn't compared, saving keeps the one of the saved version
+    let fields = match data {
+        EditorData::Blog((_, meta)) => vec![
+            (TK::Title, meta.title().clone()),
+            (TK::Summary, meta.summary().clone()),
+            (TK::Tags, tags_text(&meta.tags)),
+            (TK::Image, meta.image().clone()),
+        ],
+        EditorData::Project((_, meta)) => vec![
+            (TK::Title, meta.title().clone()),
+            (TK::Summary, meta.summary().clone()),
+            (TK::Tags, tags_text(&meta.tags)),
+            (TK::Image, meta.splash().cloned().unwrap_or_default()),
+        ],
+        EditorData::Resource(_) => return String::new(),
+    };
+    fields
+        .into_iter()
+        .map(|(label, value)| format!("{}: {}", locales_store.get(label), value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn tags_text(tags: &Tags) -> String {
+    visible_tags(tags)
+        .iter()
+        .map(|t| t.tag.clone())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
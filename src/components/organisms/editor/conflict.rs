@@ -0,0 +1,127 @@
+use super::changes::texts;
+use crate::{
+    api::{backend::Backend, blob::BlobClient, editor::EditorClient},
+    components::atoms::modal::{
+        show_modal, Buttons, ConflictData, MergeSection, ModalButton, ModalData, ModalStore,
+    },
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::{id::ResId, store::LocalStore},
+    },
+    pages::editor::EditorData,
+    utils::diff,
+};
+use petompp_web_models::models::{blob::blob_meta::BlobMetaData, country::Country};
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+/// Saves the draft, unless the resource was saved again since the draft started.
+/// Then the draft can overwrite that version or be rebased onto it.
+/// Without a draft the `loaded` version is checked, rebasing then calls `onreload`.
+pub fn save_checked(
+    resid: ResId,
+    lang: Country,
+    loaded: Option<EditorData>,
+    onsave: Callback<()>,
+    onreload: Callback<()>,
+    local_dispatch: Dispatch<LocalStore>,
+    modal_dispatch: Dispatch<ModalStore>,
+) {
+    spawn_local(async move {
+        let local_store = local_dispatch.get();
+        let mine = local_store.get(&resid, lang.key());
+        let base = match &mine {
+            Some(_) => local_store.base(&resid, lang.key()).cloned(),
+            None => loaded,
+        };
+        let Some(base) = base else {
+            return onsave.emit(());
+        };
+        let Some(theirs) = saved_since(&resid, lang, &base).await else {
+            return onsave.emit(());
+        };
+        let has_draft = mine.is_some();
+        let mine = mine.unwrap_or(base.clone());
+        let (merged, conflicts) = rebase(&base, &mine, &theirs);
+        let locales_store = Dispatch::<LocalesStore>::new().get();
+        let sections = texts(&base, &locales_store)
+            .into_iter()
+            .zip(texts(&mine, &locales_store))
+            .zip(texts(&theirs, &locales_store))
+            .zip(texts(&merged, &locales_store))
+            .map(
+                |((((label, base), (_, mine)), (_, theirs)), (_, merged))| MergeSection {
+                    label,
+                    base,
+                    mine,
+                    theirs,
+                    merged,
+                },
+            )
+            .collect();
+        let onrebase = Callback::from(move |_| {
+            if !has_draft {
+                return onreload.emit(());
+            }
+            let (resid, merged, theirs) = (resid.clone(), merged.clone(), theirs.clone());
+            local_dispatch.reduce_mut(|store| store.rebase(resid, lang.key(), merged, theirs));
+        });
+        show_modal(
+            ModalData::Conflict(ConflictData {
+                title: TK::SaveConflict,
+                message: TK::SaveConflictQuestion,
+                sections,
+                conflicts,
+                buttons: Buttons::ConfirmRiskyCancel(
+                    ModalButton::new(TK::Rebase, Some(onrebase)),
+                    ModalButton::new(TK::Overwrite, Some(onsave.reform(|_| ()))),
+                    ModalButton::new(TK::Cancel, None),
+                ),
+            }),
+            modal_dispatch,
+        );
+    });
+}
+
+/// The saved version, if it is not the one the draft started from.
+pub async fn saved_meanwhile(
+    resid: &ResId,
+    lang: Country,
+    local_store: &LocalStore,
+) -> Option<EditorData> {
+    saved_since(resid, lang, local_store.base(resid, lang.key())?).await
+}
+
+/// The saved version, if it was saved again after `base`. Blobs tell it by their
+/// update time, so only their metadata is read when they weren't.
+/// Resources have none and are compared as a whole.
+async fn saved_since(resid: &ResId, lang: Country, base: &EditorData) -> Option<EditorData> {
+    if let (ResId::Blob(blob), Some(meta)) = (resid, base.meta()) {
+        let (container, filename) = (blob.container().ok()?, blob.content_path(lang).ok()?);
+        match Backend::get_meta::<BlobMetaData>(container, &filename).await {
+            Ok(saved) if saved.updated != meta.updated => (),
+            _ => return None,
+        }
+        return Backend::get_saved(resid, lang).await.ok().flatten();
+    }
+    match Backend::get_saved(resid, lang).await {
+        Ok(Some(saved)) if &saved != base => Some(saved),
+        _ => None,
+    }
+}
+
+/// The draft's changes applied to the newer saved version, with the number of conflicts.
+/// Metadata is not merged field by field, the draft's wins if it was changed at all.
+fn rebase(base: &EditorData, mine: &EditorData, theirs: &EditorData) -> (EditorData, usize) {
+    let merge = diff::merge(&base.to_string(), &mine.to_string(), &theirs.to_string());
+    let merged = match (base, mine, theirs) {
+        (EditorData::Blog((_, b)), EditorData::Blog((_, m)), EditorData::Blog((_, t))) => {
+            EditorData::Blog((String::new(), if m == b { t } else { m }.clone()))
+        }
+        (EditorData::Project((_, b)), EditorData::Project((_, m)), EditorData::Project((_, t))) => {
+            EditorData::Project((String::new(), if m == b { t } else { m }.clone()))
+        }
+        _ => theirs.clone(),
+    };
+    (merged.with_string(merge.text), merge.conflicts)
+}
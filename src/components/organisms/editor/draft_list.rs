@@ -0,0 +1,313 @@
+use super::{
+    atoms::save_button::save_draft,
+    changes::show_changes_callback,
+    conflict::{save_checked, saved_meanwhile},
+    draft_storage::{format_size, DraftStorageWarning},
+    draft_transfer::{export_drafts, DraftImport},
+};
+use crate::{
+    components::atoms::modal::{
+        show_modal_callback, Buttons, DialogData, ModalButton, ModalData, ModalStore,
+    },
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::{
+            content::ContentType,
+            id::{BlobType, ResId, ResourceId},
+            store::{Draft, LocalStore},
+        },
+        session::SessionStore,
+    },
+    router::route::Route,
+};
+use chrono::{DateTime, Local};
+use petompp_web_models::models::country::Country;
+use std::collections::{BTreeMap, BTreeSet};
+use yew::{platform::spawn_local, prelude::*};
+use yew_router::prelude::*;
+use yewdux::prelude::*;
+
+/// Every draft kept in this browser, with actions for each one and for all of them.
+#[function_component(DraftList)]
+pub fn draft_list() -> Html {
+    let (session_store, _) = use_store::<SessionStore>();
+    let (local_store, local_dispatch) = use_store::<LocalStore>();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (_, modal_dispatch) = use_store::<ModalStore>();
+    let navigator = use_navigator().unwrap();
+    // Why the last save of a draft failed, by `draft_key`.
+    let errors = use_state_eq(BTreeMap::<String, String>::new);
+    let busy = use_state_eq(|| false);
+    // Drafts picked for the export, by `draft_key`.
+    let selected = use_state_eq(BTreeSet::<String>::new);
+    let token = session_store.token.clone().unwrap_or_default();
+    let author = session_store
+        .user
+        .as_ref()
+        .map(|u| u.name.clone())
+        .unwrap_or_default();
+    let drafts = local_store.drafts();
+    let selected_drafts = drafts
+        .iter()
+        .filter(|d| selected.contains(&draft_key(&d.resid, d.lang)))
+        .collect::<Vec<_>>();
+    let onexport = {
+        let to_export = match selected_drafts.is_empty() {
+            true => drafts.iter().collect(),
+            false => selected_drafts.clone(),
+        }
+        .into_iter()
+        .filter_map(|d| local_store.entry(&d.resid, d.lang.key()))
+        .collect::<Vec<_>>();
+        Callback::from(move |_| export_drafts(to_export.clone()))
+    };
+    let export_text = match selected_drafts.is_empty() {
+        true => TK::ExportAll,
+        false => TK::ExportSelected,
+    };
+    let save = {
+        let (token, author) = (token.clone(), author.clone());
+        let (local_dispatch, errors) = (local_dispatch.clone(), errors.clone());
+        move |draft: Draft| {
+            let (token, author) = (token.clone(), author.clone());
+            let (local_dispatch, errors) = (local_dispatch.clone(), errors.clone());
+            Callback::from(move |_: ()| {
+                let (token, author, draft) = (token.clone(), author.clone(), draft.clone());
+                let (local_dispatch, errors) = (local_dispatch.clone(), errors.clone());
+                spawn_local(async move {
+                    let key = draft_key(&draft.resid, draft.lang);
+                    let result = save_draft(
+                        &token,
+                        &author,
+                        &draft.resid,
+                        draft.lang,
+                        &draft.data,
+                        local_dispatch,
+                    )
+                    .await;
+                    let mut map = (*errors).clone();
+                    match result {
+                        Ok(_) => map.remove(&key),
+                        Err(e) => map.insert(key, e.to_string()),
+                    };
+                    errors.set(map);
+                });
+            })
+        }
+    };
+    let rows = drafts.iter().map(|draft| {
+        let key = draft_key(&draft.resid, draft.lang);
+        let ontoggle = {
+            let (selected, key) = (selected.clone(), key.clone());
+            Callback::from(move |_| {
+                let mut set = (*selected).clone();
+                if !set.remove(&key) {
+                    set.insert(key.clone());
+                }
+                selected.set(set);
+            })
+        };
+        let kind = match &draft.resid {
+            ResId::ResKey(_) => locales_store.get(TK::Resource),
+            ResId::Blob(BlobType::Blog(_)) => locales_store.get(TK::BlogPost),
+            ResId::Blob(BlobType::Project(_)) => locales_store.get(TK::Project),
+            ResId::Blob(BlobType::Content(kind, _)) => ContentType::get(kind)
+                .map(|c| locales_store.get(TK::Label(c.item)))
+                .unwrap_or(kind.clone()),
+        };
+        let modified = draft
+            .modified
+            .map(|d| DateTime::<Local>::from(d).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or("-".to_string());
+        let onopen = {
+            let navigator = navigator.clone();
+            let query = ResourceId::from((draft.resid.clone(), draft.lang));
+            Callback::from(move |_| navigator.push_with_query(&Route::Editor, &query).unwrap())
+        };
+        let onsave = {
+            let (resid, lang) = (draft.resid.clone(), draft.lang);
+            let onsave = save(draft.clone());
+            let (local_dispatch, modal_dispatch) = (local_dispatch.clone(), modal_dispatch.clone());
+            Callback::from(move |_| {
+                save_checked(
+                    resid.clone(),
+                    lang,
+                    None,
+                    onsave.clone(),
+                    Callback::noop(),
+                    local_dispatch.clone(),
+                    modal_dispatch.clone(),
+                )
+            })
+        };
+        let onsave = show_changes_callback(
+            TK::SaveChanges,
+            TK::SaveChangesQuestion,
+            Buttons::ConfirmCancel(
+                ModalButton::new(TK::Save, Some(onsave)),
+                ModalButton::new(TK::Cancel, None),
+            ),
+            draft.resid.clone(),
+            draft.lang,
+            draft.data.clone(),
+            modal_dispatch.clone(),
+        );
+        let ondiscard = {
+            let (resid, lang) = (draft.resid.clone(), draft.lang);
+            let local_dispatch = local_dispatch.clone();
+            Callback::from(move |_| {
+                local_dispatch.reduce_mut(|store| store.remove(&resid, lang.key()))
+            })
+        };
+        let ondiscard = show_changes_callback(
+            TK::DiscardChanges,
+            TK::DiscardChangesQuestion,
+            Buttons::RiskyCancel(
+                ModalButton::new(TK::Discard, Some(ondiscard)),
+                ModalButton::new(TK::Cancel, None),
+            ),
+            draft.resid.clone(),
+            draft.lang,
+            draft.data.clone(),
+            modal_dispatch.clone(),
+        );
+        html! {
+            <tr>
+                <td>
+                    <input type={"checkbox"} class={"checkbox checkbox-sm"}
+                        checked={selected.contains(&key)} onchange={ontoggle}/>
+                </td>
+                <td>{kind}</td>
+                <td class={"break-all"}>
+                    {draft.resid.id().to_string()}
+                    if let Some(error) = errors.get(&key) {
+                        <p class={"text-error text-sm"}>{error}</p>
+                    }
+                </td>
+                <td>{draft.lang.key()}</td>
+                <td>{modified}</td>
+                <td>{format_size(draft.size as u64)}</td>
+                <td class={"flex flex-row gap-2"}>
+                    <button class={"btn btn-sm"} onclick={onopen}>{locales_store.get(TK::Open)}</button>
+                    <button class={"btn btn-sm btn-success"} onclick={onsave} disabled={*busy}>{locales_store.get(TK::Save)}</button>
+                    <button class={"btn btn-sm btn-warning"} onclick={ondiscard} disabled={*busy}>{locales_store.get(TK::Discard)}</button>
+                </td>
+            </tr>
+        }
+    });
+    // Drafts whose resource was saved again meanwhile are left to be saved one by one.
+    let onsaveall = {
+        let (drafts, busy, errors) = (drafts.clone(), busy.clone(), errors.clone());
+        let local_dispatch = local_dispatch.clone();
+        Callback::from(move |_| {
+            let (drafts, busy, errors) = (drafts.clone(), busy.clone(), errors.clone());
+            let (token, author) = (token.clone(), author.clone());
+            let local_dispatch = local_dispatch.clone();
+            busy.set(true);
+            spawn_local(async move {
+                let locales_store = Dispatch::<LocalesStore>::new().get();
+                let mut map = BTreeMap::new();
+                for draft in drafts {
+                    let key = draft_key(&draft.resid, draft.lang);
+                    let local_store = local_dispatch.get();
+                    if saved_meanwhile(&draft.resid, draft.lang, &local_store)
+                        .await
+                        .is_some()
+                    {
+                        map.insert(key, locales_store.get(TK::SaveConflict));
+                        continue;
+                    }
+                    let result = save_draft(
+                        &token,
+                        &author,
+                        &draft.resid,
+                        draft.lang,
+                        &draft.data,
+                        local_dispatch.clone(),
+                    )
+                    .await;
+                    if let Err(e) = result {
+                        map.insert(key, e.to_string());
+                    }
+                }
+                errors.set(map);
+                busy.set(false);
+            });
+        })
+    };
+    let onsaveall = show_modal_callback(
+        ModalData::Dialog(DialogData {
+            title: TK::SaveAll,
+            message: TK::SaveAllQuestion,
+            buttons: Buttons::ConfirmCancel(
+                ModalButton::new(TK::SaveAll, Some(onsaveall)),
+                ModalButton::new(TK::Cancel, None),
+            ),
+        }),
+        modal_dispatch.clone(),
+    );
+    let ondiscardall = {
+        let local_dispatch = local_dispatch.clone();
+        let drafts = drafts.clone();
+        let errors = errors.clone();
+        Callback::from(move |_| {
+            local_dispatch.reduce_mut(|store| {
+                for draft in &drafts {
+                    store.remove(&draft.resid, draft.lang.key());
+                }
+            });
+            errors.set(BTreeMap::new());
+        })
+    };
+    let ondiscardall = show_modal_callback(
+        ModalData::Dialog(DialogData {
+            title: TK::DiscardAll,
+            message: TK::DiscardAllQuestion,
+            buttons: Buttons::RiskyCancel(
+                ModalButton::new(TK::DiscardAll, Some(ondiscardall)),
+                ModalButton::new(TK::Cancel, None),
+            ),
+        }),
+        modal_dispatch,
+    );
+    html! {
+        <div class={"flex flex-col lg:w-3/4 w-full mx-auto"}>
+            <div class={"flex flex-row flex-wrap gap-4 items-center mb-2"}>
+                <p class={"text-2xl font-bold font-mono"}>{locales_store.get(TK::Drafts)}</p>
+                <div class={"flex flex-row flex-wrap gap-2 ml-auto"}>
+                    <DraftImport />
+                    if !drafts.is_empty() {
+                        <button class={"btn"} onclick={onexport}>{locales_store.get(export_text)}</button>
+                        <button class={"btn btn-success"} onclick={onsaveall} disabled={*busy}>{locales_store.get(TK::SaveAll)}</button>
+                        <button class={"btn btn-warning"} onclick={ondiscardall} disabled={*busy}>{locales_store.get(TK::DiscardAll)}</button>
+                    }
+                </div>
+            </div>
+            <DraftStorageWarning show_usage={true} />
+            <table class={"table"}>
+                <thead>
+                    <tr>
+                        <th/>
+                        <th/>
+                        <th>{locales_store.get(TK::Id)}</th>
+                        <th>{locales_store.get(TK::Language)}</th>
+                        <th>{locales_store.get(TK::Updated)}</th>
+                        <th>{locales_store.get(TK::Size)}</th>
+                        <th>{locales_store.get(TK::Actions)}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    if drafts.is_empty() {
+                        <tr><td colspan={"7"}>{locales_store.get(TK::NoDrafts)}</td></tr>
+                    } else {
+                        {for rows}
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+fn draft_key(resid: &ResId, lang: Country) -> String {
+    format!("{}.{}", resid, lang.key())
+}
@@ -0,0 +1,70 @@
+use crate::data::{
+    locales::{store::LocalesStore, tk::TK},
+    resources::{drafts_db::DraftStorage, store::LocalStore},
+};
+use gloo::events::{EventListener, EventListenerOptions};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct DraftStorageWarningProps {
+    /// Also shows how much of the browser's storage is used.
+    #[prop_or_default]
+    pub show_usage: bool,
+}
+
+/// Warns when the drafts can't be stored or the storage is almost full,
+/// and asks before closing the page while the drafts are not stored.
+#[function_component(DraftStorageWarning)]
+pub fn draft_storage_warning(props: &DraftStorageWarningProps) -> Html {
+    let (storage, _) = use_store::<DraftStorage>();
+    let (local_store, _) = use_store::<LocalStore>();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let unsaved = storage.error.is_some() && local_store.count() > 0;
+    use_effect_with_deps(
+        |unsaved| {
+            let listener = unsaved.then(web_sys::window).flatten().map(|window| {
+                let options = EventListenerOptions::enable_prevent_default();
+                EventListener::new_with_options(&window, "beforeunload", options, |e| {
+                    e.prevent_default()
+                })
+            });
+            move || drop(listener)
+        },
+        unsaved,
+    );
+    let usage = storage
+        .usage
+        .map(|(usage, quota)| format!("{} / {}", format_size(usage), format_size(quota)));
+    let alert = match (&storage.error, storage.is_at_risk()) {
+        (Some(e), _) => Some((
+            "alert-error",
+            locales_store.get(TK::DraftsNotStored(e.to_string())),
+        )),
+        (None, true) => Some((
+            "alert-warning",
+            locales_store.get(TK::DraftStorageFull(usage.clone().unwrap_or_default())),
+        )),
+        (None, false) => None,
+    };
+    match (alert, usage) {
+        (Some((class, text)), _) => html! {
+            <div class={classes!("alert", "py-1", "px-2", "text-sm", class)}>
+                <span>{text}</span>
+            </div>
+        },
+        (None, Some(usage)) if props.show_usage => html! {
+            <p class={"text-sm opacity-70"}>{locales_store.get(TK::DraftStorageUsage(usage))}</p>
+        },
+        _ => html! {},
+    }
+}
+
+pub fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1048575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        1048576..=1073741823 => format!("{:.1} MB", bytes as f64 / 1048576.0),
+        _ => format!("{:.1} GB", bytes as f64 / 1073741824.0),
+    }
+}
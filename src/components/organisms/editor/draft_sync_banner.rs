@@ -0,0 +1,93 @@
+use super::changes::changes;
+use crate::{
+    components::{
+        atoms::modal::{show_modal, Buttons, DiffData, ModalData, ModalStore},
+        state::State,
+    },
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::{
+            drafts_sync::{self, DraftSync},
+            id::ResId,
+            store::LocalStore,
+        },
+    },
+    pages::editor::EditorState,
+};
+use petompp_web_models::models::country::Country;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct DraftSyncBannerProps {
+    pub resid: Option<ResId>,
+    pub lang: Option<Country>,
+    pub onstatechanged: Callback<EditorState>,
+}
+
+/// Tells when another tab changed the draft being edited here as well,
+/// to reload its version or keep the one of this tab.
+#[function_component(DraftSyncBanner)]
+pub fn draft_sync_banner(props: &DraftSyncBannerProps) -> Html {
+    let (sync, _) = use_store::<DraftSync>();
+    let (local_store, _) = use_store::<LocalStore>();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (_, modal_dispatch) = use_store::<ModalStore>();
+    let key = props
+        .resid
+        .as_ref()
+        .zip(props.lang)
+        .map(|(resid, lang)| LocalStore::key(resid, lang.key()));
+    use_effect_with_deps(
+        |key| {
+            drafts_sync::set_editing(key.clone());
+            || drafts_sync::set_editing(None)
+        },
+        key.clone(),
+    );
+    let (Some(key), Some(resid), Some(lang)) = (key, &props.resid, props.lang) else {
+        return html! {};
+    };
+    let Some(theirs) = sync.incoming.get(&key).cloned() else {
+        return html! {};
+    };
+    let onshow = theirs.map(|theirs| {
+        let sections = changes(local_store.get(resid, lang.key()).as_ref(), &theirs.data);
+        Callback::from(move |_| {
+            show_modal(
+                ModalData::Diff(DiffData {
+                    title: TK::OtherTabChanges,
+                    message: TK::OtherTabChangesMessage,
+                    sections: sections.clone(),
+                    buttons: Buttons::default(),
+                }),
+                modal_dispatch.clone(),
+            )
+        })
+    });
+    let message = match onshow {
+        Some(_) => TK::DraftChangedInOtherTab,
+        None => TK::DraftRemovedInOtherTab,
+    };
+    let onreload = {
+        let key = key.clone();
+        let onstatechanged = props.onstatechanged.clone();
+        Callback::from(move |_| {
+            // without a draft the saved version is loaded again
+            if !drafts_sync::take_theirs(&key) {
+                onstatechanged.emit(State::Ok(None));
+            }
+        })
+    };
+    let onkeep = Callback::from(move |_| drafts_sync::keep_mine(&key));
+    html! {
+        <div class={"alert alert-warning flex flex-row flex-wrap py-2 px-4"}>
+            <span class={"grow"}>{locales_store.get(message)}</span>
+            if let Some(onclick) = onshow {
+                <button class={"btn btn-sm btn-ghost"} {onclick}>{locales_store.get(TK::ShowChanges)}</button>
+            }
+            <button class={"btn btn-sm"} onclick={onreload}>{locales_store.get(TK::Reload)}</button>
+            <button class={"btn btn-sm btn-primary"} onclick={onkeep}>{locales_store.get(TK::KeepMine)}</button>
+        </div>
+    }
+}
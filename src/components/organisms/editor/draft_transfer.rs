@@ -0,0 +1,215 @@
+use super::changes::changes;
+use crate::{
+    components::{
+        atoms::{
+            loading::Loading,
+            modal::{show_modal, Buttons, DiffData, ModalData, ModalStore},
+        },
+        state::State,
+    },
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::store::{DraftEntry, LocalStore},
+    },
+    pages::editor::EditorData,
+    utils::js::download,
+};
+use chrono::{DateTime, Local, Utc};
+use petompp_web_models::models::country::Country;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::HtmlInputElement;
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+/// Drafts moved between browsers as a json file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DraftsFile {
+    pub exported: DateTime<Utc>,
+    pub drafts: Vec<DraftEntry>,
+}
+
+/// Downloads the drafts as `drafts-<date>.json`.
+pub fn export_drafts(drafts: Vec<DraftEntry>) {
+    let file = DraftsFile {
+        exported: Utc::now(),
+        drafts,
+    };
+    let json = match serde_json::to_string_pretty(&file) {
+        Ok(json) => json,
+        Err(e) => {
+            gloo::console::error!(format!("failed to export the drafts: {}", e));
+            return;
+        }
+    };
+    let name = format!("drafts-{}.json", Local::now().format("%Y-%m-%d-%H%M"));
+    download(&name, &json, "application/json");
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ImportItem {
+    entry: DraftEntry,
+    /// The draft kept here for the same resource and language.
+    existing: Option<EditorData>,
+    selected: bool,
+}
+
+impl ImportItem {
+    fn is_same(&self) -> bool {
+        self.existing.as_ref() == Some(&self.entry.data)
+    }
+}
+
+/// Picks an exported drafts file and imports the chosen drafts from it,
+/// after showing which of them would replace a different draft kept here.
+#[function_component(DraftImport)]
+pub fn draft_import() -> Html {
+    let (local_store, local_dispatch) = use_store::<LocalStore>();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (_, modal_dispatch) = use_store::<ModalStore>();
+    let preview = use_state_eq(|| State::Ok(None::<Vec<ImportItem>>));
+    let oninput = {
+        let preview = preview.clone();
+        Callback::from(move |e: InputEvent| {
+            let element = e.target_unchecked_into::<HtmlInputElement>();
+            let Some(file) = element.files().and_then(|f| f.get(0)) else {
+                return;
+            };
+            element.set_value("");
+            let preview = preview.clone();
+            let local_store = local_store.clone();
+            preview.set(State::Loading);
+            spawn_local(async move {
+                let text = JsFuture::from(file.text())
+                    .await
+                    .ok()
+                    .and_then(|t| t.as_string());
+                let Some(file) = text.and_then(|t| serde_json::from_str::<DraftsFile>(&t).ok())
+                else {
+                    preview.set(State::Err(TK::InvalidDraftsFile));
+                    return;
+                };
+                let items = file
+                    .drafts
+                    .into_iter()
+                    .filter(|entry| Country::try_from(entry.lang.as_str()).is_ok())
+                    // a hand edited file may hold a post under a resource key and the like
+                    .filter(|entry| {
+                        entry.data.fits(&entry.resid)
+                            && entry.base.as_ref().map_or(true, |b| b.fits(&entry.resid))
+                    })
+                    .map(|entry| {
+                        let existing = local_store.get(&entry.resid, &entry.lang);
+                        let selected = existing.is_none();
+                        ImportItem {
+                            entry,
+                            existing,
+                            selected,
+                        }
+                    })
+                    .collect();
+                preview.set(State::Ok(Some(items)));
+            });
+        })
+    };
+    let picker = html! {
+        <label class={"btn"}>
+            {locales_store.get(TK::Import)}
+            <input {oninput} accept={".json,application/json"} type={"file"} class={"hidden"} />
+        </label>
+    };
+    let items = match &*preview {
+        State::Ok(Some(items)) => items.clone(),
+        State::Ok(None) => return picker,
+        State::Loading => return html! { <Loading /> },
+        State::Err(e) => {
+            return html! {
+                <>
+                {picker}
+                <p class={"text-error"}>{locales_store.get(e.clone())}</p>
+                </>
+            }
+        }
+    };
+    let rows = items.iter().enumerate().map(|(i, item)| {
+        let ontoggle = {
+            let (preview, items) = (preview.clone(), items.clone());
+            Callback::from(move |_| {
+                let mut items = items.clone();
+                items[i].selected = !items[i].selected;
+                preview.set(State::Ok(Some(items)));
+            })
+        };
+        let status = match &item.existing {
+            None => html! { <span class={"badge badge-success"}>{locales_store.get(TK::NewDraft)}</span> },
+            Some(_) if item.is_same() => html! { <span class={"badge"}>{locales_store.get(TK::SameDraft)}</span> },
+            Some(existing) => {
+                let sections = changes(Some(existing), &item.entry.data);
+                let modal_dispatch = modal_dispatch.clone();
+                let onclick = Callback::from(move |_| {
+                    show_modal(
+                        ModalData::Diff(DiffData {
+                            title: TK::ReplacesDraft,
+                            message: TK::ReplacesDraftMessage,
+                            sections: sections.clone(),
+                            buttons: Buttons::default(),
+                        }),
+                        modal_dispatch.clone(),
+                    )
+                });
+                html! {
+                    <button class={"badge badge-warning"} {onclick}>{locales_store.get(TK::ReplacesDraft)}</button>
+                }
+            }
+        };
+        let modified = item
+            .entry
+            .modified
+            .map(|d| DateTime::<Local>::from(d).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or("-".to_string());
+        html! {
+            <tr>
+                <td>
+                    <input type={"checkbox"} class={"checkbox checkbox-sm"} checked={item.selected}
+                        disabled={item.is_same()} onchange={ontoggle}/>
+                </td>
+                <td class={"break-all"}>{item.entry.resid.to_string()}</td>
+                <td>{&item.entry.lang}</td>
+                <td>{modified}</td>
+                <td>{status}</td>
+            </tr>
+        }
+    });
+    let onimport = {
+        let (preview, items) = (preview.clone(), items.clone());
+        Callback::from(move |_| {
+            local_dispatch.reduce_mut(|store| {
+                for item in items.iter().filter(|i| i.selected && !i.is_same()) {
+                    store.import(item.entry.clone());
+                }
+            });
+            preview.set(State::Ok(None));
+        })
+    };
+    let oncancel = {
+        let preview = preview.clone();
+        Callback::from(move |_| preview.set(State::Ok(None)))
+    };
+    html! {
+        <div class={"flex flex-col gap-2 w-full rounded-box bg-base-200 p-4"}>
+            <p class={"font-semibold"}>{locales_store.get(TK::ImportPreview)}</p>
+            <table class={"table table-sm"}>
+                <tbody>
+                    {for rows}
+                </tbody>
+            </table>
+            <div class={"flex flex-row gap-2 justify-end"}>
+                <button class={"btn btn-neutral"} onclick={oncancel}>{locales_store.get(TK::Cancel)}</button>
+                <button class={"btn btn-primary"} onclick={onimport}
+                    disabled={!items.iter().any(|i| i.selected && !i.is_same())}>
+                    {locales_store.get(TK::ImportSelected)}
+                </button>
+            </div>
+        </div>
+    }
+}
@@ -1 +1,8 @@
 pub mod atoms;
+pub mod changes;
+pub mod conflict;
+pub mod draft_list;
+pub mod draft_storage;
+pub mod draft_sync_banner;
+pub mod draft_transfer;
+pub mod revision_history;
@@ -0,0 +1,167 @@
+use crate::{
+    api::{
+        backend::Backend,
+        revision::{Revision, RevisionClient, RevisionInfo},
+    },
+    components::{
+        atoms::{
+            diff_view::{DiffMode, DiffModeSelect, DiffView},
+            loading::Loading,
+            modal::{show_modal_callback, Buttons, DialogData, ModalButton, ModalData, ModalStore},
+        },
+        state::State,
+    },
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::{id::ResId, publication::Publishable},
+    },
+    hooks::abort::use_abort_scope,
+    pages::editor::EditorData,
+};
+use chrono::{DateTime, Local};
+use petompp_web_models::models::country::Country;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct RevisionHistoryProps {
+    pub resid: ResId,
+    pub lang: Country,
+    /// What the editor holds now, revisions are compared to it.
+    pub current: EditorData,
+    pub onrestore: Callback<EditorData>,
+}
+
+/// Saved revisions of the resource, with the changes made since each one.
+#[function_component(RevisionHistory)]
+pub fn revision_history(props: &RevisionHistoryProps) -> Html {
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (_, modal_dispatch) = use_store::<ModalStore>();
+    let revisions = use_state_eq(|| State::Ok(None));
+    let selected = use_state_eq(|| None::<(RevisionInfo, State<Revision, String>)>);
+    let mode = use_state_eq(DiffMode::default);
+    let abort = use_abort_scope();
+    {
+        let revisions = revisions.clone();
+        let selected = selected.clone();
+        let abort = abort.clone();
+        use_effect_with_deps(
+            move |(resid, lang)| {
+                let (resid, lang) = (resid.clone(), *lang);
+                selected.set(None);
+                revisions.set(State::Loading);
+                abort.spawn(async move {
+                    match Backend::get_revisions(&resid, lang).await {
+                        Ok(list) => revisions.set(State::Ok(Some(list))),
+                        Err(e) => revisions.set(State::Err(e)),
+                    }
+                });
+            },
+            (props.resid.clone(), props.lang),
+        );
+    }
+    let list = match &*revisions {
+        State::Ok(Some(list)) if list.is_empty() => html! {
+            <p class={"italic"}>{locales_store.get(TK::NoRevisions)}</p>
+        },
+        State::Ok(Some(list)) => {
+            let items = list.iter().map(|info| {
+                let active = matches!(&*selected, Some((s, _)) if s == info);
+                let onclick = {
+                    let info = info.clone();
+                    let selected = selected.clone();
+                    let abort = abort.clone();
+                    Callback::from(move |_| {
+                        let info = info.clone();
+                        let selected = selected.clone();
+                        selected.set(Some((info.clone(), State::Loading)));
+                        abort.spawn(async move {
+                            let revision = match Backend::get_revision(&info).await {
+                                Ok(revision) => State::Ok(revision),
+                                Err(e) => State::Err(e.to_string()),
+                            };
+                            selected.set(Some((info, revision)));
+                        });
+                    })
+                };
+                let date = DateTime::<Local>::from(info.created).format("%Y-%m-%d %H:%M:%S");
+                html! {
+                    <li>
+                        <a class={classes!(active.then_some("active"))} {onclick}>
+                            <span class={"font-mono"}>{date.to_string()}</span>
+                            <span class={"opacity-60"}>{&info.author}</span>
+                        </a>
+                    </li>
+                }
+            });
+            html! {
+                <ul class={"menu bg-base-200 rounded-box lg:w-72 w-full max-h-96 overflow-y-auto flex-nowrap"}>
+                    {for items}
+                </ul>
+            }
+        }
+        State::Loading | State::Ok(None) => html! { <Loading /> },
+        State::Err(e) => html! { <p class={"text-error"}>{e.to_string()}</p> },
+    };
+    let details = match &*selected {
+        Some((_, State::Ok(revision))) => {
+            let old = revision.data.to_string();
+            let new = props.current.to_string();
+            let onrestore = {
+                let onrestore = props.onrestore.clone();
+                let data = keep_status(revision.data.clone(), &props.current);
+                Callback::from(move |_| onrestore.emit(data.clone()))
+            };
+            let onrestore = show_modal_callback(
+                ModalData::Dialog(DialogData {
+                    title: TK::RestoreRevision,
+                    message: TK::RestoreRevisionQuestion,
+                    buttons: Buttons::RiskyCancel(
+                        ModalButton::new(TK::Restore, Some(onrestore)),
+                        ModalButton::new(TK::Cancel, None),
+                    ),
+                }),
+                modal_dispatch,
+            );
+            let onmodechange = {
+                let mode = mode.clone();
+                Callback::from(move |m| mode.set(m))
+            };
+            html! {
+                <div class={"flex flex-col gap-2 grow min-w-0"}>
+                    <div class={"flex flex-row flex-wrap gap-4 items-center"}>
+                        <p class={"font-semibold"}>{locales_store.get(TK::ChangesSinceRevision)}</p>
+                        <DiffModeSelect mode={*mode} onchange={onmodechange}/>
+                        <button class={"btn btn-sm btn-warning ml-auto"} onclick={onrestore}>
+                            {locales_store.get(TK::Restore)}
+                        </button>
+                    </div>
+                    <DiffView {old} {new} mode={*mode}/>
+                </div>
+            }
+        }
+        Some((_, State::Loading)) => html! { <Loading /> },
+        Some((_, State::Err(e))) => html! { <p class={"text-error"}>{e}</p> },
+        None => html! {},
+    };
+    html! {
+        <div class={"flex lg:flex-row flex-col gap-4"}>
+            {list}
+            {details}
+        </div>
+    }
+}
+
+/// Restoring brings back the content and metadata, but not whether it was published.
+fn keep_status(mut data: EditorData, current: &EditorData) -> EditorData {
+    match (&mut data, current) {
+        (EditorData::Blog((_, meta)), EditorData::Blog((_, curr))) => {
+            meta.set_status(curr.is_draft(), curr.publish_at())
+        }
+        (EditorData::Project((_, meta)), EditorData::Project((_, curr))) => {
+            meta.set_status(curr.is_draft(), curr.publish_at())
+        }
+        _ => {}
+    }
+    data
+}
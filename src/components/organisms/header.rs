@@ -4,7 +4,9 @@ use crate::{
         organisms::menu::Menu,
     },
     data::{
+        config::ConfigStore,
         locales::{store::LocalesStore, tk::TK},
+        resources::content::ContentType,
         session::SessionStore,
     },
     router::route::Route,
@@ -17,14 +19,18 @@ use yewdux::prelude::use_store;
 #[function_component(Header)]
 pub fn header() -> Html {
     let (session_store, _) = use_store::<SessionStore>();
+    let (config_store, _) = use_store::<ConfigStore>();
     let (locales_store, locales_dispatch) = use_store::<LocalesStore>();
     let onselectedchanged = Callback::from(move |c| locales_dispatch.reduce_mut(|s| s.curr = c));
     let editor_btn = match &session_store.user {
-        Some(u) if u.role == RoleData::Admin => Some(
+        Some(u) if u.role == RoleData::Admin && config_store.config().features.editor => Some(
             html! {<Link<Route> to={Route::Editor} classes={"btn btn-accent rounded-none rounded-b-lg"}>{locales_store.get(TK::Editor)}</Link<Route>>},
         ),
         _ => None,
     };
+    let content_btns = ContentType::enabled().into_iter().map(|c| {
+        html! {<Link<Route> to={Route::ContentList { kind: c.key.to_string() }} classes={"btn btn-neutral rounded-none rounded-b-lg"}>{locales_store.get(TK::Label(c.title))}</Link<Route>>}
+    });
     html! {
         <div class={"sticky top-0 z-50 w-full bg-base-200 opacity-90 pb-2"}>
         <div class={"w-full flex flex-row px-2 pt-1 m-auto"}>
@@ -38,6 +44,7 @@ pub fn header() -> Html {
                         <Link<Route> to={Route::Home} classes={"btn btn-neutral rounded-none rounded-b-lg"}>{locales_store.get(TK::Home)}</Link<Route>>
                         <Link<Route> to={Route::Projects} classes={"btn btn-neutral rounded-none rounded-b-lg"}>{locales_store.get(TK::Projects)}</Link<Route>>
                         <Link<Route> to={Route::Blog} classes={"btn btn-neutral rounded-none rounded-b-lg"}>{locales_store.get(TK::Blog)}</Link<Route>>
+                        {for content_btns}
                         {editor_btn}
                         <Link<Route> to={Route::About} classes={"btn btn-neutral rounded-none rounded-b-lg"}>{locales_store.get(TK::About)}</Link<Route>>
                         <Link<Route> to={Route::Contact} classes={"btn btn-neutral rounded-none rounded-b-lg"}>{locales_store.get(TK::Contact)}</Link<Route>>
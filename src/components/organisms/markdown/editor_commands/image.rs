@@ -1,6 +1,9 @@
 use super::command::{insert_after_selection, EditorCommand};
 use crate::{
-    api::{blob::BlobClient, client::ApiClient},
+    api::{
+        asset::AssetKind,
+        container::{Container, ImageUpload},
+    },
     components::atoms::modal::{
         show_modal_callback, Buttons, ModalButton, ModalData, ModalStore, MODAL_FIELD_PREFIX,
     },
@@ -46,12 +49,9 @@ impl EditorCommand for ImageCommand {
             if url.is_empty() {
                 return;
             }
-            let full_url: String = web_sys::js_sys::encode_uri(
-                &<ApiClient as BlobClient>::get_url("image-upload", &url),
-            )
-            .into();
-            let image = format!("![{}]({})", &url, &full_url);
-            cb.emit(insert_after_selection(&id, &image));
+            let full_url = ImageUpload::url(&url);
+            let asset = AssetKind::from_name(&url).markdown(&url, &full_url);
+            cb.emit(insert_after_selection(&id, &asset));
         });
         let modal_data = ModalData::ImageSelector(Buttons::ConfirmCancel(
             ModalButton::new(TK::Insert, Some(onclick)),
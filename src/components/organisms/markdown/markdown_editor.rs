@@ -1,14 +1,26 @@
-use crate::components::atoms::modal::ModalStore;
-use crate::components::organisms::markdown::editor_commands::command::get_commands;
+use crate::api::abort::AbortScope;
+use crate::api::asset::AssetKind;
+use crate::api::client::UploadProgress;
+use crate::api::container::{Container, ImageUpload};
+use crate::api::image::upload_image;
+use crate::components::atoms::modal::{show_error, ModalStore};
+use crate::components::atoms::upload_progress::UploadProgressBar;
+use crate::components::organisms::markdown::editor_commands::command::{
+    get_commands, insert_after_selection,
+};
+use crate::data::session::SessionStore;
 use crate::hooks::event::use_event;
 use crate::pages::editor::EditorData;
 use crate::utils::js::{get_textarea, set_textarea_height, set_textarea_text};
 use crate::utils::style::get_svg_bg_mask_style;
-use web_sys::HtmlInputElement;
+use wasm_bindgen::JsCast;
+use web_sys::{ClipboardEvent, File, FileList, HtmlInputElement};
 use yew::prelude::*;
 use yewdux::prelude::*;
 
 const TEXTAREA_ID: &str = "editor-textarea";
+/// Folder in the [`ImageUpload`] container for files pasted or dropped into the editor.
+const PASTED_DIR: &str = "pasted";
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct MarkdownEditorProps {
@@ -42,14 +54,116 @@ pub fn markdown_editor(props: &MarkdownEditorProps) -> Html {
             onchanged.emit(state.clone().with_string(new_value));
         })
     };
+    let (session_store, _) = use_store::<SessionStore>();
+    let upload = use_state(|| None::<(AbortScope, UploadProgress)>);
+    // the upload in flight is cancelled when the editor unmounts
+    let upload_scope = use_mut_ref(|| None::<AbortScope>);
+    {
+        let upload_scope = upload_scope.clone();
+        use_effect_with_deps(
+            move |_| {
+                move || {
+                    if let Some(scope) = upload_scope.borrow_mut().take() {
+                        scope.abort();
+                    }
+                }
+            },
+            (),
+        );
+    }
+    let upload_file = {
+        let upload = upload.clone();
+        let onchanged = onchanged.clone();
+        Callback::from(move |file: File| {
+            let scope = AbortScope::default();
+            *upload_scope.borrow_mut() = Some(scope.clone());
+            upload.set(Some((
+                scope.clone(),
+                UploadProgress {
+                    sent: 0,
+                    total: file.size() as u64,
+                },
+            )));
+            let onprogress = {
+                let upload = upload.clone();
+                let scope = scope.clone();
+                Callback::from(move |p| upload.set(Some((scope.clone(), p))))
+            };
+            let upload = upload.clone();
+            let onchanged = onchanged.clone();
+            let token = session_store.token.clone().unwrap_or_default();
+            scope.spawn(async move {
+                let id = web_sys::window().unwrap().crypto().unwrap().random_uuid();
+                let result = upload_image(
+                    &token,
+                    ImageUpload::NAME,
+                    &format!("{}/{}-{}", PASTED_DIR, &id[..8], file.name()),
+                    &file,
+                    onprogress,
+                )
+                .await;
+                upload.set(None);
+                match result {
+                    Ok(filename) => {
+                        let url = ImageUpload::url(&filename);
+                        let asset = AssetKind::from_name(&filename).markdown(&filename, &url);
+                        onchanged.emit(insert_after_selection(TEXTAREA_ID, &asset));
+                    }
+                    Err(e) => show_error(e.to_string(), None),
+                }
+            });
+        })
+    };
+    let onpaste = {
+        let upload_file = upload_file.clone();
+        Callback::from(move |e: Event| {
+            let files = e
+                .dyn_ref::<ClipboardEvent>()
+                .and_then(|e| e.clipboard_data())
+                .and_then(|d| d.files());
+            if let Some(file) = first_asset(files) {
+                e.prevent_default();
+                upload_file.emit(file);
+            }
+        })
+    };
+    let ondragover = Callback::from(|e: DragEvent| e.prevent_default());
+    let ondrop = Callback::from(move |e: DragEvent| {
+        if let Some(file) = first_asset(e.data_transfer().and_then(|d| d.files())) {
+            e.prevent_default();
+            upload_file.emit(file);
+        }
+    });
+    let upload_bar = (*upload).clone().map(|(scope, progress)| {
+        let oncancel = {
+            let upload = upload.clone();
+            Callback::from(move |_| {
+                scope.abort();
+                upload.set(None);
+            })
+        };
+        html! {
+            <div class={"flex px-4 py-2 border-b"}>
+                <UploadProgressBar {progress} {oncancel}/>
+            </div>
+        }
+    });
     html! {
         <div class={"border border rounded-2xl shadow-2xl"}>
             <EditorCommands {onchanged} />
-            <textarea id={TEXTAREA_ID} {oninput} class={"w-full font-mono bg-base-100 outline-none p-4 rounded-lg overflow-hidden resize-none leading-normal"}></textarea>
+            {upload_bar}
+            <textarea id={TEXTAREA_ID} {oninput} {onpaste} {ondragover} {ondrop} class={"w-full font-mono bg-base-100 outline-none p-4 rounded-lg overflow-hidden resize-none leading-normal"}></textarea>
         </div>
     }
 }
 
+fn first_asset(files: Option<FileList>) -> Option<File> {
+    let files = files?;
+    (0..files.length())
+        .filter_map(|i| files.get(i))
+        .find(|f| ImageUpload::INFO.accepts(&f.name(), &f.type_()))
+}
+
 #[derive(Clone, PartialEq, Properties)]
 struct EditorCommandsProps {
     pub onchanged: Callback<String>,
@@ -1,6 +1,6 @@
 use crate::{
     components::atoms::{link::HrefLink, markdown::Markdown},
-    data::resources::id::{BlobType, ResId},
+    data::resources::id::ResId,
     pages::{
         blog_post::BlogPostMeta,
         editor::{EditorData, EditorDataState},
@@ -24,10 +24,7 @@ pub fn markdown_preview(props: &MarkdownPreviewProps) -> Html {
         + "//"
         + location.host().unwrap().as_str()
         + match &props.data.id.0 {
-            ResId::Blob(id) => match &id {
-                BlobType::Blog(_) => (Route::BlogPost { id: id.to_string() }).to_path(),
-                BlobType::Project(_) => (Route::Project { id: id.to_string() }).to_path(),
-            },
+            ResId::Blob(id) => Route::from(id).to_path(),
             ResId::ResKey(id) => "/".to_string() + id.trim_end_matches("-content"),
         }
         .as_str();
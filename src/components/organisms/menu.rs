@@ -1,7 +1,9 @@
 use crate::{
     components::organisms::user_box::UserBox,
     data::{
+        config::ConfigStore,
         locales::{store::LocalesStore, tk::TK},
+        resources::content::ContentType,
         session::SessionStore,
     },
     router::route::Route,
@@ -52,6 +54,7 @@ fn menu_button() -> Html {
 #[function_component(MenuDropdown)]
 fn menu_dropdown() -> Html {
     let (session_store, _) = use_store::<SessionStore>();
+    let (config_store, _) = use_store::<ConfigStore>();
     let (locales_store, _) = use_store::<LocalesStore>();
     let navigator = use_navigator().unwrap();
     let get_onclick = |route: Route| {
@@ -61,11 +64,14 @@ fn menu_dropdown() -> Html {
         })
     };
     let editor_btn = match &session_store.user {
-        Some(u) if u.role == RoleData::Admin => Some(
+        Some(u) if u.role == RoleData::Admin && config_store.config().features.editor => Some(
             html! {<a onclick={get_onclick.clone()(Route::Editor)} class={"lg:hidden btn btn-accent rounded-none rounded-r-lg"}>{locales_store.get(TK::Editor)}</a>},
         ),
         _ => None,
     };
+    let content_btns = ContentType::enabled().into_iter().map(|c| {
+        html! {<a onclick={get_onclick.clone()(Route::ContentList { kind: c.key.to_string() })} class={"lg:hidden btn btn-neutral rounded-none rounded-r-lg"}>{locales_store.get(TK::Label(c.title))}</a>}
+    });
     html! {
         <div class={"drawer-side z-10"}>
             <label for={"menu-drawer"} class={"drawer-overlay"} />
@@ -73,6 +79,7 @@ fn menu_dropdown() -> Html {
                 <a onclick={get_onclick.clone()(Route::Home)} class={"lg:hidden btn btn-neutral rounded-none rounded-r-lg"}>{locales_store.get(TK::Home)}</a>
                 <a onclick={get_onclick.clone()(Route::Projects)} class={"lg:hidden btn btn-neutral rounded-none rounded-r-lg"}>{locales_store.get(TK::Projects)}</a>
                 <a onclick={get_onclick.clone()(Route::Blog)} class={"lg:hidden btn btn-neutral rounded-none rounded-r-lg"}>{locales_store.get(TK::Blog)}</a>
+                {for content_btns}
                 {editor_btn}
                 <a onclick={get_onclick.clone()(Route::About)} class={"lg:hidden btn btn-neutral rounded-none rounded-r-lg"}>{locales_store.get(TK::About)}</a>
                 <a onclick={get_onclick.clone()(Route::Contact)} class={"lg:hidden btn btn-neutral rounded-none rounded-r-lg"}>{locales_store.get(TK::Contact)}</a>
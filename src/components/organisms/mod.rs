@@ -2,6 +2,7 @@ pub mod admin;
 pub mod blob_image_select;
 pub mod blob_tags_input;
 pub mod blog;
+pub mod data_recovery;
 pub mod editor;
 pub mod header;
 pub mod image_directory_browser;
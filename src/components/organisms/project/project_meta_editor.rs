@@ -1,4 +1,5 @@
 use crate::{
+    api::container::{images_dir, Container, Project},
     components::{
         atoms::text_input::{InputType, TextInput, TextareaInput},
         organisms::{
@@ -6,15 +7,21 @@ use crate::{
             image_link_input::ImageLinkInput,
         },
     },
-    data::locales::{store::LocalesStore, tk::TK},
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::publication::{visible_tags, Publishable},
+    },
 };
-use petompp_web_models::models::blob::project::ProjectMetaData;
+use petompp_web_models::models::{blob::project::ProjectMetaData, tag::Tags};
 use yew::prelude::*;
 use yewdux::prelude::*;
 
 #[derive(Clone, Properties, PartialEq)]
 pub struct ProjectMetaEditorProps {
     pub data: ProjectMetaData,
+    /// Container of the project, or of the content type shown like one.
+    #[prop_or(Project::NAME)]
+    pub container: &'static str,
     pub ondatachanged: Callback<ProjectMetaData>,
 }
 
@@ -46,7 +53,7 @@ pub fn project_meta_editor(props: &ProjectMetaEditorProps) -> Html {
         let ondatachanged = ondatachanged.clone();
         ondatachanged.reform(move |value| {
             let mut data = data.clone();
-            data.tags = value;
+            data.set_visible_tags(value);
             data
         })
     };
@@ -59,8 +66,8 @@ pub fn project_meta_editor(props: &ProjectMetaEditorProps) -> Html {
             data
         })
     };
-    let container = "project".to_string();
-    let folder = Some(data.id().to_string() + "/images/");
+    let container = props.container.to_string();
+    let folder = Some(images_dir(data.id()));
     let splash = data.splash().cloned();
 
     html! {
@@ -77,7 +84,7 @@ pub fn project_meta_editor(props: &ProjectMetaEditorProps) -> Html {
                 value={props.data.summary().clone()}
                 onchange={summary_onchange}
                 error={false}/>
-            <BlobTagsInput data={props.data.tags.clone()} ondatachanged={tags_onchange}/>
+            <BlobTagsInput data={Tags::from(visible_tags(&props.data.tags))} ondatachanged={tags_onchange}/>
             <ImageDirectoryBrowser container={container.clone()} folder={folder.clone()}/>
             <ImageLinkInput {container} {folder} data={splash} ondatachanged={splash_onchange}/>
         </>
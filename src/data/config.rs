@@ -0,0 +1,190 @@
+use petompp_web_models::models::country::Country;
+use serde::Deserialize;
+use yewdux::prelude::*;
+
+lazy_static::lazy_static! {
+    static ref API_URL: String = with_trailing_slash(std::option_env!("API_URL").unwrap_or_default());
+    static ref AZURE_STORAGE_URL: String =
+        with_trailing_slash(std::option_env!("AZURE_STORAGE_URL").unwrap_or_default());
+}
+
+/// Deployment settings read from `/config.json` or `/config.yml` on startup,
+/// so one build can be promoted between environments.
+/// Missing values fall back to the ones provided at compile time.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub api_url: Option<String>,
+    pub storage_url: Option<String>,
+    /// How blob urls are built from the storage url.
+    pub storage: StorageKind,
+    /// Language key used on the first visit, before the user picks one.
+    pub default_lang: Option<String>,
+    pub features: Features,
+    pub uploads: Uploads,
+    pub images: Images,
+    pub backend: BackendKind,
+    /// Seed data for the in-memory backend, defaults to `/fixtures/demo.yml`.
+    pub fixtures: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Uploads {
+    /// Blobs larger than this many bytes are uploaded in blocks of this size.
+    /// Requires the API's block endpoints, `None` or `0` sends every blob at once.
+    pub chunk_size: Option<usize>,
+    /// How many files dropped on the blob browser are uploaded at once.
+    pub parallel: usize,
+}
+
+impl Uploads {
+    /// Smaller blocks would only multiply the requests.
+    pub const MIN_CHUNK_SIZE: usize = 256 * 1024;
+
+    /// The configured block size raised to [`Self::MIN_CHUNK_SIZE`], `None` when turned off.
+    pub fn block_size(&self) -> Option<usize> {
+        self.chunk_size
+            .filter(|&size| size > 0)
+            .map(|size| size.max(Self::MIN_CHUNK_SIZE))
+    }
+}
+
+impl Default for Uploads {
+    fn default() -> Self {
+        Self {
+            chunk_size: None,
+            parallel: 3,
+        }
+    }
+}
+
+/// Uploaded images are downscaled to fit these bounds and re-encoded.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Images {
+    pub max_width: u32,
+    pub max_height: u32,
+    /// Bounding square of the thumbnails stored next to the images.
+    pub thumbnail_size: u32,
+    /// Encoder quality between 0 and 1, values outside are clamped.
+    pub quality: f64,
+}
+
+impl Default for Images {
+    fn default() -> Self {
+        Self {
+            max_width: 1920,
+            max_height: 1920,
+            thumbnail_size: 800,
+            quality: 0.85,
+        }
+    }
+}
+
+/// Where the data comes from. `memory` runs the site without an API,
+/// from the fixtures file, for demos and offline development.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    #[default]
+    Api,
+    Memory,
+}
+
+/// Layout of the blob urls, see [`crate::api::storage`].
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum StorageKind {
+    #[default]
+    Azure,
+    /// Path style urls, containers are prefixes in the bucket when it is set.
+    S3 {
+        #[serde(default)]
+        bucket: Option<String>,
+    },
+    /// Folder served along with the app, ignores the storage url.
+    Local {
+        #[serde(default = "default_local_dir")]
+        dir: String,
+    },
+}
+
+fn default_local_dir() -> String {
+    "fixtures/storage".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Features {
+    pub editor: bool,
+    /// Keys of the registered content types to show, their containers have to exist.
+    pub content: Vec<String>,
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self {
+            editor: true,
+            content: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn api_url(&self) -> String {
+        match &self.api_url {
+            Some(url) => with_trailing_slash(url),
+            None => API_URL.clone(),
+        }
+    }
+
+    pub fn storage_url(&self) -> String {
+        match &self.storage_url {
+            Some(url) => with_trailing_slash(url),
+            None => AZURE_STORAGE_URL.clone(),
+        }
+    }
+
+    pub fn fixtures(&self) -> String {
+        self.fixtures
+            .clone()
+            .unwrap_or_else(|| "/fixtures/demo.yml".to_string())
+    }
+
+    pub fn default_lang(&self) -> Option<Country> {
+        self.default_lang
+            .as_deref()
+            .and_then(|l| Country::try_from(l).ok())
+    }
+}
+
+/// `None` until the config file has been fetched.
+#[derive(Default, PartialEq, Clone, Debug, Store)]
+pub struct ConfigStore(Option<Config>);
+
+impl ConfigStore {
+    pub fn is_loaded(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub fn load(&mut self, config: Config) {
+        self.0 = Some(config);
+    }
+
+    pub fn config(&self) -> Config {
+        self.0.clone().unwrap_or_default()
+    }
+
+    /// Config for code running outside of components, like the API clients.
+    pub fn get() -> Config {
+        Dispatch::<Self>::new().get().config()
+    }
+}
+
+fn with_trailing_slash(url: &str) -> String {
+    match url {
+        url if url.ends_with('/') => url.to_string(),
+        url => format!("{}/", url),
+    }
+}
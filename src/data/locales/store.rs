@@ -1,4 +1,9 @@
 use super::tk::TK;
+use crate::data::{
+    persist::{self, Area, Migration, Persisted, Versioned},
+    resources::content::ContentType,
+};
+use gloo::storage::{LocalStorage, Storage};
 use petompp_web_models::models::country::Country;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -6,8 +11,7 @@ use strum::IntoEnumIterator;
 use wasm_bindgen::{prelude::Closure, JsCast};
 use yewdux::prelude::*;
 
-#[derive(PartialEq, Clone, Debug, Store, Serialize, Deserialize)]
-#[store(storage = "local")]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub struct LocalesStore {
     pub curr: Country,
     #[serde(skip_serializing, skip_deserializing)]
@@ -26,6 +30,24 @@ impl Default for LocalesStore {
     }
 }
 
+impl Store for LocalesStore {
+    fn new() -> Self {
+        persist::load()
+    }
+
+    fn should_notify(&self, old: &Self) -> bool {
+        self != old
+    }
+}
+
+impl Versioned for LocalesStore {
+    const MIGRATIONS: &'static [Migration] = &[persist::unversioned];
+}
+
+impl Persisted for LocalesStore {
+    const AREA: Area = Area::Local;
+}
+
 impl LocalesStore {
     pub fn is_loaded(&self, country: Country) -> bool {
         match country {
@@ -34,6 +56,15 @@ impl LocalesStore {
         }
     }
 
+    /// Whether an earlier visit has already saved the store.
+    pub fn is_persisted() -> bool {
+        LocalStorage::raw()
+            .get_item(std::any::type_name::<Self>())
+            .ok()
+            .flatten()
+            .is_some()
+    }
+
     pub fn get(&self, key: TK) -> String {
         let val = match self.curr {
             Country::Poland => self.pl.get(&key.to_string()),
@@ -44,6 +75,15 @@ impl LocalesStore {
         match key {
             TK::ActivateUserQuestion(s)
             | TK::DeleteUserQuestion(s)
+            | TK::StillReferencedBy(s)
+            | TK::ReferencesNotChecked(s)
+            | TK::RevisionNotKept(s)
+            | TK::NotScanned(s)
+            | TK::DraftStorageUsage(s)
+            | TK::DraftStorageFull(s)
+            | TK::DraftsNotStored(s)
+            | TK::UsedBy(s)
+            | TK::DeleteUsedQuestion(s)
             | TK::Username_OnlyAlphanumericOrSelectedChars(s)
             | TK::Username_NameTaken(s)
             | TK::E_Auth_MissingClaim(s)
@@ -94,7 +134,15 @@ impl LocalesStore {
     }
 
     fn validate_data(data: &HashMap<String, String>) -> Result<(), DataDiff> {
-        let tks = TK::iter().map(|tk| tk.to_string()).collect::<HashSet<_>>();
+        let tks = TK::iter()
+            .filter(|tk| !matches!(tk, TK::Label(_)))
+            .map(|tk| tk.to_string())
+            .chain(
+                ContentType::all()
+                    .flat_map(ContentType::labels)
+                    .map(String::from),
+            )
+            .collect::<HashSet<_>>();
         let data_tks = data.keys().cloned().collect::<HashSet<_>>();
         let missing = tks.difference(&data_tks).cloned().collect::<Vec<_>>();
         let extra = data_tks.difference(&tks).cloned().collect::<Vec<_>>();
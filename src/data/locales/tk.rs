@@ -17,12 +17,75 @@ pub enum TK {
     Url,
     Text,
     Gallery,
+    Attachments,
     Save,
     SaveChanges,
     SaveChangesQuestion,
     Discard,
     DiscardChanges,
     DiscardChangesQuestion,
+    Draft,
+    Publish,
+    PublishQuestion,
+    Unpublish,
+    UnpublishQuestion,
+    Schedule,
+    ScheduleQuestion,
+    Scheduled,
+    PublishAt,
+    PublishesIn,
+    Upcoming,
+    NoUpcoming,
+    Revisions,
+    NoRevisions,
+    Restore,
+    RestoreRevision,
+    RestoreRevisionQuestion,
+    ChangesSinceRevision,
+    RevisionNotKept(String),
+    Lines,
+    Words,
+    SideBySide,
+    NoChanges,
+    SaveConflict,
+    SaveConflictQuestion,
+    DraftChanges,
+    SavedChanges,
+    MergedChanges,
+    MergeConflicts,
+    Rebase,
+    Drafts,
+    NoDrafts,
+    Language,
+    Size,
+    Open,
+    SaveAll,
+    SaveAllQuestion,
+    DiscardAll,
+    DiscardAllQuestion,
+    ExportAll,
+    ExportSelected,
+    Import,
+    ImportPreview,
+    ImportSelected,
+    InvalidDraftsFile,
+    NewDraft,
+    SameDraft,
+    ReplacesDraft,
+    ReplacesDraftMessage,
+    DraftStorageUsage(String),
+    DraftStorageFull(String),
+    DraftsNotStored(String),
+    DataNotMigrated,
+    DraftChangedInOtherTab,
+    DraftRemovedInOtherTab,
+    OtherTabChanges,
+    OtherTabChangesMessage,
+    ShowChanges,
+    Reload,
+    KeepMine,
+    DiscardRecoveredQuestion,
+    Download,
     Logout,
     LogoutQuestion,
     Login,
@@ -80,8 +143,31 @@ pub enum TK {
     Creating,
     Created,
     Updated,
+    Uploading,
+    Rename,
+    EnterNewName,
+    StillReferencedBy(String),
+    ReferencesNotChecked(String),
+    UsedBy(String),
+    DeleteUsedQuestion(String),
+    UnusedFiles,
+    NoUnusedFiles,
+    DeleteUnusedFiles,
+    DeleteUnusedFilesQuestion,
+    NotScanned(String),
+    Queued,
+    Uploaded,
+    Cancelled,
+    SkippedExists,
+    CancelAll,
+    ClearFinished,
+    IfFileExists,
+    KeepBoth,
+    Overwrite,
+    Skip,
     NothingSelected,
     ErrorOccured,
+    RequestTimedOut,
     Username_InvalidLength(i32, i32),
     Username_OnlyAlphanumericOrSelectedChars(String),
     Username_NameTaken(String),
@@ -105,10 +191,16 @@ pub enum TK {
     E_Validation_ResourceData_KeyMismatch(String, String),
     E_Validation_ResourceData_KeyMissing,
     E_Validation_ResourceData_ValueMissing,
+    /// Key declared outside of this enum, like the labels of a
+    /// [`ContentType`](crate::data::resources::content::ContentType).
+    Label(&'static str),
 }
 
 impl std::fmt::Display for TK {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Self::Label(key) = self {
+            return write!(f, "{}", key);
+        }
         let val = format!("{:?}", self);
         if let Some(pos) = val.find('(') {
             write!(f, "{}", &val[..pos])
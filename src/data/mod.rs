@@ -1,4 +1,6 @@
-pub mod blob;
+pub mod config;
 pub mod locales;
+pub mod persist;
 pub mod resources;
 pub mod session;
+pub mod upload;
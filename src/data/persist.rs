@@ -0,0 +1,410 @@
+//! Persists stores in the browser storage with a schema version, so data saved
+//! by an older version is migrated instead of failing to deserialize.
+//! Data that can't be migrated is kept aside to be downloaded, see [`Recovery`].
+use chrono::{DateTime, Utc};
+use gloo::{
+    events::EventListener,
+    storage::{LocalStorage, SessionStorage, Storage},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{marker::PhantomData, rc::Rc};
+use wasm_bindgen::JsCast;
+use web_sys::StorageEvent;
+use yewdux::{
+    listener::{init_listener, Listener},
+    prelude::*,
+};
+
+/// Turns the data of one version into the next one.
+pub type Migration = fn(Value) -> Result<Value, String>;
+
+/// Marks the keys the data that couldn't be migrated is kept under,
+/// `<key>.failed.<milliseconds>`, so data failing again doesn't replace the one kept before.
+/// Earlier versions kept it under `<key>.failed`.
+const FAILED_SUFFIX: &str = ".failed";
+
+/// Data with a schema version and the migrations from the older ones.
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// `MIGRATIONS[i]` turns version `i` into `i + 1`, version 0 being the data
+    /// saved before it had a version. Append a migration for every change that
+    /// breaks deserializing the saved data.
+    const MIGRATIONS: &'static [Migration];
+    const VERSION: u32 = Self::MIGRATIONS.len() as u32;
+}
+
+/// A store kept in the browser storage, see [`load`].
+pub trait Persisted: Versioned + Store + Default {
+    const AREA: Area;
+
+    fn key() -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Area {
+    Local,
+    Session,
+}
+
+impl Area {
+    fn storage(&self) -> web_sys::Storage {
+        match self {
+            Self::Local => LocalStorage::raw(),
+            Self::Session => SessionStorage::raw(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Envelope {
+    version: u32,
+    state: Value,
+}
+
+/// Migration from the data saved before it had a version, when its shape didn't change.
+pub fn unversioned(state: Value) -> Result<Value, String> {
+    Ok(state)
+}
+
+pub fn encode<T: Versioned>(state: &T) -> Result<String, String> {
+    let state = serde_json::to_value(state).map_err(|e| e.to_string())?;
+    serde_json::to_string(&Envelope {
+        version: T::VERSION,
+        state,
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Reads data of any older version, running the migrations it missed.
+pub fn decode<T: Versioned>(raw: &str) -> Result<T, String> {
+    let value = serde_json::from_str::<Value>(raw).map_err(|e| e.to_string())?;
+    let (version, mut state) = match serde_json::from_value::<Envelope>(value.clone()) {
+        Ok(envelope) => (envelope.version, envelope.state),
+        Err(_) => (0, value),
+    };
+    if version > T::VERSION {
+        return Err(format!(
+            "saved by a newer version ({} > {})",
+            version,
+            T::VERSION
+        ));
+    }
+    for (from, migration) in T::MIGRATIONS.iter().enumerate().skip(version as usize) {
+        state = migration(state).map_err(|e| format!("migration from {}: {}", from, e))?;
+    }
+    serde_json::from_value(state).map_err(|e| e.to_string())
+}
+
+/// Reads the store and saves it on every change, to be called in [`Store::new`].
+/// Data that can't be read is kept aside and the store starts over.
+pub fn load<T: Persisted>() -> T {
+    let (state, unkept) = match read::<T>() {
+        Ok(state) => (state, None),
+        Err(unkept) => (None, Some(unkept)),
+    };
+    init_listener(PersistListener::<T> {
+        unkept,
+        _store: PhantomData,
+    });
+    state.unwrap_or_default()
+}
+
+/// Reloads the store when another tab changes it.
+pub fn init_tab_sync<T: Persisted>() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    EventListener::new(&window, "storage", |e| {
+        let Some(e) = e.dyn_ref::<StorageEvent>() else {
+            return;
+        };
+        if e.key().as_deref() != Some(T::key()) {
+            return;
+        }
+        let Some(raw) = e.new_value() else {
+            return;
+        };
+        // the tab that saved it keeps it aside if it has to be
+        match decode::<T>(&raw) {
+            Ok(state) => Dispatch::<T>::new().set(state),
+            Err(e) => gloo::console::warn!(format!("failed to sync {}: {}", T::key(), e)),
+        }
+    })
+    .forget();
+}
+
+/// The saved store, or the data and the error when it couldn't be read nor kept aside.
+fn read<T: Persisted>() -> Result<Option<T>, (String, String)> {
+    let Some(raw) = T::AREA.storage().get_item(T::key()).ok().flatten() else {
+        return Ok(None);
+    };
+    match decode(&raw) {
+        Ok(state) => Ok(Some(state)),
+        Err(e) if keep_saved::<T>(&raw, e.clone()) => Ok(None),
+        Err(e) => Err((raw, e)),
+    }
+}
+
+/// Keeps the saved store aside and removes it, so the next load doesn't keep it again.
+fn keep_saved<T: Persisted>(raw: &str, error: String) -> bool {
+    let kept = keep(T::AREA, T::key(), raw, error);
+    if kept {
+        let _ = T::AREA.storage().remove_item(T::key());
+    }
+    kept
+}
+
+struct PersistListener<T> {
+    /// Saved data that couldn't be read nor kept aside, not saved over until it is kept.
+    unkept: Option<(String, String)>,
+    _store: PhantomData<T>,
+}
+
+impl<T: Persisted> Listener for PersistListener<T> {
+    type Store = T;
+
+    fn on_change(&mut self, state: Rc<Self::Store>) {
+        if let Some((raw, error)) = &self.unkept {
+            if !keep_saved::<T>(raw, error.clone()) {
+                return;
+            }
+            self.unkept = None;
+        }
+        let result = encode(state.as_ref()).and_then(|json| {
+            T::AREA
+                .storage()
+                .set_item(T::key(), &json)
+                .map_err(|e| format!("{:?}", e))
+        });
+        if let Err(e) = result {
+            gloo::console::error!(format!("failed to save {}: {}", T::key(), e));
+        }
+    }
+}
+
+/// Data that couldn't be migrated, as kept aside.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailedData {
+    pub error: String,
+    pub failed: DateTime<Utc>,
+    pub raw: String,
+}
+
+/// Keeps the data under a key of its own, see [`FAILED_SUFFIX`], where it isn't overwritten.
+/// Returns whether it was kept, so the original can be deleted.
+pub fn keep(area: Area, key: &str, raw: &str, error: String) -> bool {
+    gloo::console::error!(format!("failed to read {}: {}", key, error));
+    let data = FailedData {
+        error,
+        failed: Utc::now(),
+        raw: raw.to_string(),
+    };
+    let storage = area.storage();
+    let mut millis = data.failed.timestamp_millis();
+    while let Ok(Some(_)) = storage.get_item(&failed_key(key, millis)) {
+        millis += 1;
+    }
+    let result = serde_json::to_string(&data)
+        .map_err(|e| e.to_string())
+        .and_then(|json| {
+            storage
+                .set_item(&failed_key(key, millis), &json)
+                .map_err(|e| format!("{:?}", e))
+        });
+    match result {
+        Ok(_) => {
+            Dispatch::<Recovery>::new().set(Recovery::scan());
+            true
+        }
+        Err(e) => {
+            gloo::console::error!(format!("failed to keep {}: {}", key, e));
+            false
+        }
+    }
+}
+
+fn failed_key(key: &str, millis: i64) -> String {
+    format!("{}{}.{}", key, FAILED_SUFFIX, millis)
+}
+
+/// The key the data kept under `failed_key` was saved under.
+fn original_key(failed_key: &str) -> Option<&str> {
+    let (key, rest) = failed_key.rsplit_once(FAILED_SUFFIX)?;
+    match rest.strip_prefix('.') {
+        Some(millis) if millis.parse::<i64>().is_ok() => Some(key),
+        None if rest.is_empty() => Some(key),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recovered {
+    pub area: Area,
+    /// The key the data was saved under.
+    pub key: String,
+    /// The key it is kept under.
+    pub failed_key: String,
+    pub data: FailedData,
+}
+
+impl Recovered {
+    /// The key without the module path of the store.
+    pub fn name(&self) -> &str {
+        self.key.rsplit("::").next().unwrap_or(&self.key)
+    }
+
+    /// Deletes the kept data.
+    pub fn discard(&self) {
+        let _ = self.area.storage().remove_item(&self.failed_key);
+    }
+}
+
+/// Every data kept aside by [`keep`], until it is discarded.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Recovery {
+    pub items: Vec<Recovered>,
+}
+
+impl Store for Recovery {
+    fn new() -> Self {
+        Self::scan()
+    }
+
+    fn should_notify(&self, old: &Self) -> bool {
+        self != old
+    }
+}
+
+impl Recovery {
+    pub fn scan() -> Self {
+        let mut items = [Area::Local, Area::Session]
+            .into_iter()
+            .flat_map(|area| {
+                let storage = area.storage();
+                (0..storage.length().unwrap_or_default())
+                    .filter_map(|i| storage.key(i).ok().flatten())
+                    .filter_map(|failed_key| {
+                        let key = original_key(&failed_key)?.to_string();
+                        let json = storage.get_item(&failed_key).ok().flatten()?;
+                        let data = serde_json::from_str(&json).ok()?;
+                        Some(Recovered {
+                            area,
+                            key,
+                            failed_key,
+                            data,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| a.data.failed.cmp(&b.data.failed));
+        Self { items }
+    }
+
+    /// Deletes the data kept under `failed_key`.
+    pub fn discard(&mut self, failed_key: &str) {
+        self.items.retain(|item| {
+            if item.failed_key == failed_key {
+                item.discard();
+            }
+            item.failed_key != failed_key
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Note {
+        title: String,
+        pinned: bool,
+    }
+
+    /// Saved as `{ "name": .. }` first, then `name` became `title` and `pinned` was added.
+    impl Versioned for Note {
+        const MIGRATIONS: &'static [Migration] = &[unversioned, rename_name, add_pinned];
+    }
+
+    fn rename_name(mut state: Value) -> Result<Value, String> {
+        let name = state
+            .as_object_mut()
+            .and_then(|s| s.remove("name"))
+            .ok_or("no name")?;
+        state["title"] = name;
+        Ok(state)
+    }
+
+    fn add_pinned(mut state: Value) -> Result<Value, String> {
+        state["pinned"] = json!(false);
+        Ok(state)
+    }
+
+    fn note() -> Note {
+        Note {
+            title: "a".to_string(),
+            pinned: true,
+        }
+    }
+
+    #[test]
+    fn encoded_data_has_the_current_version() {
+        let raw = encode(&note()).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&raw).unwrap(),
+            json!({ "version": 3, "state": { "title": "a", "pinned": true } })
+        );
+        assert_eq!(decode::<Note>(&raw).unwrap(), note());
+    }
+
+    #[test]
+    fn unversioned_data_runs_every_migration() {
+        assert_eq!(
+            decode::<Note>(r#"{ "name": "a" }"#).unwrap(),
+            Note {
+                title: "a".to_string(),
+                pinned: false,
+            }
+        );
+    }
+
+    #[test]
+    fn versioned_data_runs_the_missed_migrations() {
+        let raw = r#"{ "version": 2, "state": { "title": "a" } }"#;
+        assert_eq!(
+            decode::<Note>(raw).unwrap(),
+            Note {
+                title: "a".to_string(),
+                pinned: false,
+            }
+        );
+    }
+
+    #[test]
+    fn failed_migrations_tell_the_version() {
+        let raw = r#"{ "version": 1, "state": { "title": "a" } }"#;
+        assert_eq!(
+            decode::<Note>(raw).unwrap_err(),
+            "migration from 1: no name"
+        );
+    }
+
+    #[test]
+    fn newer_data_is_not_read() {
+        let raw = r#"{ "version": 4, "state": { "title": "a", "pinned": true } }"#;
+        assert!(decode::<Note>(raw).is_err());
+    }
+
+    #[test]
+    fn failed_keys_lead_back_to_the_original() {
+        assert_eq!(original_key(&failed_key("a::B", 1700)), Some("a::B"));
+        assert_eq!(original_key("a::B.failed"), Some("a::B"));
+        assert_eq!(original_key("a::B"), None);
+        assert_eq!(original_key("a::B.failed.x"), None);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    api::container::{content_dir, content_path, images_dir, CONTAINERS},
+    data::{config::ConfigStore, resources::publication::Publishable},
+};
+use petompp_web_models::models::{
+    blob::{blob_meta::BlobMetaData, blog::BlogMetaData, project::ProjectMetaData},
+    country::Country,
+};
+
+/// Metadata shapes content can be stored with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MetaKind {
+    /// Title, summary and cover image, shown like a blog post.
+    Blog,
+    /// Title, summary and the files in its `images/` folder, shown like a project.
+    Project,
+}
+
+/// Markdown content with metadata, kept in its own container. Set as the
+/// [`Container::CONTENT`](crate::api::container::Container::CONTENT) of a container
+/// listed in [`CONTAINERS`], it gets the editor, local drafts and the list and
+/// detail pages, once enabled in the config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentType {
+    /// Used in resource ids and urls, has to differ from `reskey`, `blog` and `prj`.
+    pub key: &'static str,
+    /// Container holding the content, laid out like the blog one.
+    pub container: &'static str,
+    pub meta: MetaKind,
+    /// Name of the section, in the header and on the list page.
+    /// The labels are keys in the locale files, shown with
+    /// [`TK::Label`](crate::data::locales::tk::TK::Label).
+    pub title: &'static str,
+    /// Name of a single piece, in the editor.
+    pub item: &'static str,
+    pub new_item: &'static str,
+    pub back: &'static str,
+}
+
+impl ContentType {
+    pub fn get(key: &str) -> Option<&'static Self> {
+        Self::all().find(|c| c.key == key)
+    }
+
+    pub fn all() -> impl Iterator<Item = &'static Self> {
+        CONTAINERS.iter().filter_map(|c| c.content)
+    }
+
+    /// Types listed in `"features": { "content": [...] }`.
+    pub fn enabled() -> Vec<&'static Self> {
+        let enabled = ConfigStore::get().features.content;
+        Self::all()
+            .filter(|c| enabled.iter().any(|k| k == c.key))
+            .collect()
+    }
+
+    pub fn get_enabled(key: &str) -> Option<&'static Self> {
+        Self::enabled().into_iter().find(|c| c.key == key)
+    }
+
+    /// Keys of the labels, the locale files are checked for them like for the
+    /// [`TK`](crate::data::locales::tk::TK) ones.
+    pub fn labels(&self) -> [&'static str; 4] {
+        [self.title, self.item, self.new_item, self.back]
+    }
+
+    pub fn dir(&self, id: &str) -> String {
+        content_dir(id)
+    }
+
+    pub fn content_path(&self, id: &str, lang: Country) -> String {
+        content_path(id, lang)
+    }
+
+    pub fn images_dir(&self, id: &str) -> String {
+        images_dir(id)
+    }
+}
+
+/// Metadata of a piece of content, in the shape its [`MetaKind`] sets.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentMeta {
+    Blog(BlogMetaData),
+    Project(ProjectMetaData),
+}
+
+impl ContentMeta {
+    pub fn id(&self) -> String {
+        match self {
+            Self::Blog(m) => m.id().to_string(),
+            Self::Project(m) => m.id().to_string(),
+        }
+    }
+
+    pub fn lang(&self) -> Country {
+        match self {
+            Self::Blog(m) => m.lang(),
+            Self::Project(m) => m.lang(),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Blog(m) => m.title().clone(),
+            Self::Project(m) => m.title().clone(),
+        }
+    }
+
+    pub fn filename(&self) -> String {
+        match self {
+            Self::Blog(m) => m.filename.clone(),
+            Self::Project(m) => m.filename.clone(),
+        }
+    }
+
+    /// Cover image in the image upload container, if the shape has one.
+    pub fn image(&self) -> Option<String> {
+        match self {
+            Self::Blog(m) => Some(m.image().to_string()).filter(|i| !i.is_empty()),
+            Self::Project(_) => None,
+        }
+    }
+}
+
+impl Publishable for ContentMeta {
+    fn blob(&self) -> &BlobMetaData {
+        match self {
+            Self::Blog(m) => m.blob(),
+            Self::Project(m) => m.blob(),
+        }
+    }
+
+    fn blob_mut(&mut self) -> &mut BlobMetaData {
+        match self {
+            Self::Blog(m) => m.blob_mut(),
+            Self::Project(m) => m.blob_mut(),
+        }
+    }
+}
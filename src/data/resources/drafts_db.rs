@@ -0,0 +1,404 @@
+//! Keeps the drafts of the [`LocalStore`] in IndexedDB, one record per draft,
+//! as localStorage fits only a few MB for the whole site.
+use super::{
+    drafts_sync,
+    store::{DraftEntry, LocalStore},
+};
+use crate::data::persist::{self, Area};
+use futures::channel::oneshot;
+use gloo::{
+    events::EventListener,
+    storage::{LocalStorage, Storage},
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    fmt::Display,
+    rc::Rc,
+};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    js_sys::{Array, Reflect},
+    DomException, EventTarget, IdbDatabase, IdbOpenDbRequest, IdbRequest, IdbTransactionMode,
+};
+use yew::platform::spawn_local;
+use yewdux::{listener::Listener, prelude::*};
+
+const DB_NAME: &str = "drafts";
+const DB_VERSION: u32 = 1;
+const STORE_NAME: &str = "drafts";
+/// Share of the quota above which the drafts are at risk.
+const QUOTA_WARNING: f64 = 0.9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbError {
+    Unavailable,
+    QuotaExceeded,
+    Other(String),
+}
+
+impl Display for DbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unavailable => f.write_str("IndexedDB is not available"),
+            Self::QuotaExceeded => f.write_str("the storage quota is exceeded"),
+            Self::Other(e) => f.write_str(e),
+        }
+    }
+}
+
+/// How the drafts are stored, to warn before they are lost.
+#[derive(Debug, Default, Clone, PartialEq, Store)]
+pub struct DraftStorage {
+    /// The stored drafts were read into the [`LocalStore`].
+    pub loaded: bool,
+    /// Bytes used and available to the site, as estimated by the browser.
+    pub usage: Option<(u64, u64)>,
+    /// Why the last change of the drafts was not stored.
+    pub error: Option<DbError>,
+}
+
+impl DraftStorage {
+    /// Changes made from now on may not survive closing the page.
+    pub fn is_at_risk(&self) -> bool {
+        self.error.is_some()
+            || self.usage.map_or(false, |(usage, quota)| {
+                usage as f64 >= quota as f64 * QUOTA_WARNING
+            })
+    }
+}
+
+/// Reads the stored drafts into the [`LocalStore`], moving over the ones
+/// kept in localStorage by earlier versions.
+pub fn load() {
+    spawn_local(async move {
+        let legacy_key = std::any::type_name::<LocalStore>();
+        let legacy = LocalStorage::raw()
+            .get_item(legacy_key)
+            .ok()
+            .flatten()
+            .and_then(|raw| match persist::decode::<LocalStore>(&raw) {
+                Ok(legacy) => Some(legacy),
+                Err(e) => {
+                    if persist::keep(Area::Local, legacy_key, &raw, e) {
+                        LocalStorage::delete(legacy_key);
+                    }
+                    None
+                }
+            });
+        let result = async {
+            let db = open().await?;
+            let (mut entries, failed) = read_all(&db).await?;
+            if !failed.is_empty() {
+                write(&db, failed.into_iter().map(|k| (k, None)).collect()).await?;
+            }
+            if let Some(legacy) = &legacy {
+                let stored = entries.keys().cloned().collect::<Vec<_>>();
+                let moved = legacy
+                    .entries()
+                    .into_iter()
+                    .filter(|(key, _)| !stored.contains(key))
+                    .collect::<BTreeMap<_, _>>();
+                write(
+                    &db,
+                    moved
+                        .iter()
+                        .map(|(k, e)| (k.clone(), Some(e.clone())))
+                        .collect(),
+                )
+                .await?;
+                entries.extend(moved);
+                LocalStorage::delete(legacy_key);
+            }
+            Ok::<_, DbError>(entries)
+        }
+        .await;
+        let (stored, error) = match result {
+            Ok(entries) => (Some(entries), None),
+            Err(e) => (None, Some(e)),
+        };
+        // without the database the old drafts are still there, at least for this visit
+        let entries = stored
+            .clone()
+            .or_else(|| legacy.map(|l| l.entries()))
+            .unwrap_or_default();
+        if let Some(stored) = &stored {
+            drafts_sync::remember(stored.iter().map(|(k, e)| (k, Some(e))));
+        }
+        let dispatch = Dispatch::<LocalStore>::new();
+        dispatch.reduce_mut(|store| {
+            for entry in entries.into_values() {
+                // changed before the stored one was read
+                if store.get(&entry.resid, &entry.lang).is_none() {
+                    store.import(entry);
+                }
+            }
+        });
+        Dispatch::<DraftStorage>::new().reduce_mut(|s| {
+            s.loaded = true;
+            s.error = error;
+        });
+        if let Some(stored) = stored {
+            let unsaved = dispatch
+                .get()
+                .entries()
+                .into_iter()
+                .filter(|(k, e)| stored.get(k) != Some(e))
+                .map(|(k, e)| (k, Some(e)))
+                .collect::<BTreeMap<_, _>>();
+            if !unsaved.is_empty() {
+                drafts_sync::send(&unsaved);
+                queue(unsaved);
+            }
+            persist().await;
+        }
+        update_usage().await;
+    });
+}
+
+/// Stores the drafts changed since the last call and sends them to the other tabs,
+/// see [`LocalStore`].
+#[derive(Default)]
+pub struct DraftsListener {
+    last: Rc<LocalStore>,
+}
+
+impl Listener for DraftsListener {
+    type Store = LocalStore;
+
+    fn on_change(&mut self, state: Rc<Self::Store>) {
+        // only the changed drafts are built, as a keystroke changes one of them
+        let changes = state
+            .changed_since(&self.last)
+            .into_iter()
+            .map(|key| {
+                let entry =
+                    LocalStore::key_lang(&key).and_then(|(resid, lang)| state.entry(&resid, lang));
+                (key, entry)
+            })
+            .collect::<BTreeMap<_, _>>();
+        self.last = state;
+        // the changes of the other tabs were stored by them
+        if changes.is_empty()
+            || drafts_sync::is_applying()
+            || !Dispatch::<DraftStorage>::new().get().loaded
+        {
+            return;
+        }
+        drafts_sync::send(&changes);
+        queue(changes);
+    }
+}
+
+thread_local! {
+    /// Changes waiting to be written, the newest one for each draft.
+    static PENDING: RefCell<BTreeMap<String, Option<DraftEntry>>> = RefCell::new(BTreeMap::new());
+    static FLUSHING: Cell<bool> = Cell::new(false);
+    /// Opened once, until another tab needs it closed to upgrade it.
+    static DB: RefCell<Option<IdbDatabase>> = RefCell::new(None);
+}
+
+/// Writes the changes after the ones already being written, so they land in order.
+pub(super) fn queue(changes: BTreeMap<String, Option<DraftEntry>>) {
+    PENDING.with(|p| p.borrow_mut().extend(changes));
+    if FLUSHING.with(|f| f.replace(true)) {
+        return;
+    }
+    spawn_local(async move {
+        let mut error = None;
+        loop {
+            let changes = PENDING.with(|p| std::mem::take(&mut *p.borrow_mut()));
+            if changes.is_empty() {
+                break;
+            }
+            error = match open().await {
+                Ok(db) => write(&db, changes).await.err(),
+                Err(e) => Some(e),
+            };
+        }
+        FLUSHING.with(|f| f.set(false));
+        Dispatch::<DraftStorage>::new().reduce_mut(|s| s.error = error);
+        update_usage().await;
+    });
+}
+
+async fn open() -> Result<IdbDatabase, DbError> {
+    if let Some(db) = DB.with(|d| d.borrow().clone()) {
+        return Ok(db);
+    }
+    let factory = web_sys::window()
+        .and_then(|w| w.indexed_db().ok().flatten())
+        .ok_or(DbError::Unavailable)?;
+    let request: IdbOpenDbRequest = factory
+        .open_with_u32(DB_NAME, DB_VERSION)
+        .map_err(|_| DbError::Unavailable)?;
+    let _upgrade = EventListener::once(&request, "upgradeneeded", move |e| {
+        let Some(request) = e
+            .target()
+            .and_then(|t| t.dyn_into::<IdbOpenDbRequest>().ok())
+        else {
+            return;
+        };
+        if let Ok(db) = request.result().map(|r| r.unchecked_into::<IdbDatabase>()) {
+            let _ = db.create_object_store(STORE_NAME);
+        }
+    });
+    // an older version open in another tab delays the upgrade until it is closed there
+    let _blocked = EventListener::new(&request, "blocked", |_| {
+        gloo::console::warn!("waiting for the other tabs to close the drafts database")
+    });
+    wait(&request, &["success"], &["error"]).await?;
+    let db: IdbDatabase = request
+        .result()
+        .map(|r| r.unchecked_into())
+        .map_err(|_| DbError::Unavailable)?;
+    for event in ["versionchange", "close"] {
+        EventListener::once(&db, event, |e| {
+            if let Some(db) = e.target().and_then(|t| t.dyn_into::<IdbDatabase>().ok()) {
+                db.close();
+            }
+            DB.with(|d| d.borrow_mut().take());
+        })
+        .forget();
+    }
+    DB.with(|d| *d.borrow_mut() = Some(db.clone()));
+    Ok(db)
+}
+
+/// Every stored draft and the keys of the ones that couldn't be read,
+/// which are kept aside by [`persist::keep`] and can be deleted.
+async fn read_all(
+    db: &IdbDatabase,
+) -> Result<(BTreeMap<String, DraftEntry>, Vec<String>), DbError> {
+    let store = db
+        .transaction_with_str(STORE_NAME)
+        .and_then(|t| t.object_store(STORE_NAME))
+        .map_err(js_error)?;
+    let keys = store.get_all_keys().map_err(js_error)?;
+    let values = store.get_all().map_err(js_error)?;
+    let (keys_done, values_done) = futures::join!(
+        wait(&keys, &["success"], &["error"]),
+        wait(&values, &["success"], &["error"])
+    );
+    keys_done.and(values_done)?;
+    let (keys, values) = (
+        keys.result().map_err(js_error)?,
+        values.result().map_err(js_error)?,
+    );
+    // both are sorted by key
+    let mut entries = BTreeMap::new();
+    let mut failed = Vec::new();
+    for (key, value) in Array::from(&keys).iter().zip(Array::from(&values).iter()) {
+        let (Some(key), Some(value)) = (key.as_string(), value.as_string()) else {
+            continue;
+        };
+        match persist::decode::<DraftEntry>(&value) {
+            Ok(entry) => {
+                entries.insert(LocalStore::key(&entry.resid, &entry.lang), entry);
+            }
+            Err(e) => {
+                if persist::keep(Area::Local, &format!("drafts/{}", key), &value, e) {
+                    failed.push(key);
+                }
+            }
+        }
+    }
+    Ok((entries, failed))
+}
+
+/// Puts or, for `None`, deletes the drafts in one transaction.
+async fn write(
+    db: &IdbDatabase,
+    changes: BTreeMap<String, Option<DraftEntry>>,
+) -> Result<(), DbError> {
+    let transaction = db
+        .transaction_with_str_and_mode(STORE_NAME, IdbTransactionMode::Readwrite)
+        .map_err(js_error)?;
+    let store = transaction.object_store(STORE_NAME).map_err(js_error)?;
+    for (key, entry) in changes {
+        let key = JsValue::from_str(&key);
+        match entry {
+            Some(entry) => {
+                let json = persist::encode(&entry).map_err(DbError::Other)?;
+                store.put_with_key(&JsValue::from_str(&json), &key)
+            }
+            None => store.delete(&key),
+        }
+        .map_err(js_error)?;
+    }
+    wait(&transaction, &["complete"], &["abort", "error"]).await?;
+    match transaction.error() {
+        Some(e) if e.name() == "QuotaExceededError" => Err(DbError::QuotaExceeded),
+        Some(e) => Err(DbError::Other(e.message())),
+        None => Ok(()),
+    }
+}
+
+/// Resolves on the first of the events, with an error for the `err` ones.
+async fn wait(target: &EventTarget, ok: &[&str], err: &[&str]) -> Result<(), DbError> {
+    let (sender, receiver) = oneshot::channel();
+    let sender = Rc::new(RefCell::new(Some(sender)));
+    let _listeners = ok
+        .iter()
+        .map(|e| (e, true))
+        .chain(err.iter().map(|e| (e, false)))
+        .map(|(event, success)| {
+            let sender = sender.clone();
+            EventListener::once(target, event.to_string(), move |_| {
+                if let Some(sender) = sender.borrow_mut().take() {
+                    let _ = sender.send(success);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    match receiver.await {
+        Ok(true) => Ok(()),
+        Ok(false) => match target
+            .dyn_ref::<IdbRequest>()
+            .and_then(|r| r.error().ok().flatten())
+        {
+            Some(e) if e.name() == "QuotaExceededError" => Err(DbError::QuotaExceeded),
+            Some(e) => Err(DbError::Other(e.message())),
+            // transactions are checked by the caller
+            None => Ok(()),
+        },
+        Err(_) => Err(DbError::Other("cancelled".to_string())),
+    }
+}
+
+fn js_error(e: JsValue) -> DbError {
+    match e.dyn_ref::<DomException>() {
+        Some(e) if e.name() == "QuotaExceededError" => DbError::QuotaExceeded,
+        Some(e) => DbError::Other(e.message()),
+        None => DbError::Other(format!("{:?}", e)),
+    }
+}
+
+/// Asks the browser not to evict the drafts when it runs low on space.
+async fn persist() {
+    let Some(promise) = web_sys::window().and_then(|w| w.navigator().storage().persist().ok())
+    else {
+        return;
+    };
+    let _ = JsFuture::from(promise).await;
+}
+
+async fn update_usage() {
+    let Some(promise) = web_sys::window().and_then(|w| w.navigator().storage().estimate().ok())
+    else {
+        return;
+    };
+    let Ok(estimate) = JsFuture::from(promise).await else {
+        return;
+    };
+    let get = |field: &str| {
+        Reflect::get(&estimate, &JsValue::from_str(field))
+            .ok()
+            .and_then(|v| v.as_f64())
+            .map(|v| v as u64)
+    };
+    if let (Some(usage), Some(quota)) = (get("usage"), get("quota")) {
+        Dispatch::<DraftStorage>::new().reduce_mut(|s| s.usage = Some((usage, quota)));
+    }
+}
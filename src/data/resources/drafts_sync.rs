@@ -0,0 +1,222 @@
+//! Shares the changes of the drafts with the other tabs through a `BroadcastChannel`,
+//! as IndexedDB, unlike localStorage, doesn't tell them about its changes.
+use super::{
+    drafts_db,
+    store::{DraftEntry, LocalStore},
+};
+use crate::data::persist;
+use chrono::{DateTime, Utc};
+use gloo::events::EventListener;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{BroadcastChannel, MessageEvent};
+use yewdux::prelude::*;
+
+const CHANNEL_NAME: &str = "drafts";
+
+/// Drafts changed in the other tabs that are not applied here yet.
+#[derive(Debug, Default, Clone, PartialEq, Store)]
+pub struct DraftSync {
+    /// Key of the draft open in the editor of this tab.
+    pub editing: Option<String>,
+    /// Changes of the other tabs to the edited draft, waiting for a choice as the
+    /// draft was changed here too or there is none here. `None` when it was removed there.
+    pub incoming: BTreeMap<String, Option<DraftEntry>>,
+}
+
+thread_local! {
+    static CHANNEL: Option<BroadcastChannel> = BroadcastChannel::new(CHANNEL_NAME).ok();
+    /// Changes of the other tabs are being applied, so they are neither stored nor sent back.
+    static APPLYING: Cell<bool> = Cell::new(false);
+    /// When each draft was changed in the version last sent or received, sent as the base
+    /// of the next change, to tell whether the receiving tab changed the draft since.
+    static SYNCED: RefCell<BTreeMap<String, Option<DateTime<Utc>>>> = RefCell::new(BTreeMap::new());
+}
+
+/// A change of one draft, as sent to the other tabs.
+#[derive(Serialize, Deserialize)]
+struct Change {
+    /// The draft as encoded by [`persist::encode`], `None` when it was removed.
+    entry: Option<String>,
+    /// When the draft it replaces was changed.
+    base: Option<DateTime<Utc>>,
+}
+
+/// Starts applying the changes sent by the other tabs.
+pub fn init() {
+    CHANNEL.with(|channel| {
+        let Some(channel) = channel else {
+            gloo::console::warn!("drafts are not synced between tabs");
+            return;
+        };
+        EventListener::new(channel, "message", |e| {
+            let Some(data) = e
+                .dyn_ref::<MessageEvent>()
+                .and_then(|e| e.data().as_string())
+            else {
+                return;
+            };
+            match serde_json::from_str::<BTreeMap<String, Change>>(&data) {
+                Ok(changes) => receive(changes),
+                Err(e) => gloo::console::error!(format!("invalid drafts message: {}", e)),
+            }
+        })
+        .forget();
+    });
+}
+
+pub(super) fn is_applying() -> bool {
+    APPLYING.with(|a| a.get())
+}
+
+/// Notes the drafts as shared by every tab, `None` for the removed ones.
+pub(super) fn remember<'a>(drafts: impl IntoIterator<Item = (&'a String, Option<&'a DraftEntry>)>) {
+    SYNCED.with(|s| {
+        let mut synced = s.borrow_mut();
+        for (key, entry) in drafts {
+            match entry {
+                Some(entry) => synced.insert(key.clone(), entry.modified),
+                None => synced.remove(key),
+            };
+        }
+    });
+}
+
+/// Sends the changes made in this tab to the other ones.
+pub(super) fn send(changes: &BTreeMap<String, Option<DraftEntry>>) {
+    let message = changes
+        .iter()
+        .map(|(key, entry)| {
+            let entry = entry.as_ref().map(persist::encode).transpose()?;
+            let base = SYNCED.with(|s| s.borrow().get(key).copied().flatten());
+            Ok((key.clone(), Change { entry, base }))
+        })
+        .collect::<Result<BTreeMap<_, _>, String>>()
+        .and_then(|message| serde_json::to_string(&message).map_err(|e| e.to_string()));
+    remember(changes.iter().map(|(k, e)| (k, e.as_ref())));
+    let message = match message {
+        Ok(message) => message,
+        Err(e) => {
+            gloo::console::error!(format!("failed to send the drafts: {}", e));
+            return;
+        }
+    };
+    CHANNEL.with(|channel| {
+        if let Some(channel) = channel {
+            let _ = channel.post_message(&JsValue::from_str(&message));
+        }
+    });
+}
+
+/// Applies the changes, except to the edited draft when it changed here
+/// since the version the other tab changed, or there is none here.
+fn receive(changes: BTreeMap<String, Change>) {
+    let sync_dispatch = Dispatch::<DraftSync>::new();
+    let editing = sync_dispatch.get().editing.clone();
+    let (mut conflicting, mut applied) = (BTreeMap::new(), BTreeMap::new());
+    for (key, change) in changes {
+        let entry = match change
+            .entry
+            .map(|e| persist::decode::<DraftEntry>(&e))
+            .transpose()
+        {
+            Ok(entry) => entry,
+            Err(e) => {
+                gloo::console::error!(format!("invalid draft {} from another tab: {}", key, e));
+                continue;
+            }
+        };
+        match editing.as_ref() == Some(&key) && conflicts(&key, entry.as_ref(), change.base) {
+            true => conflicting.insert(key, entry),
+            false => applied.insert(key, entry),
+        };
+    }
+    sync_dispatch.reduce_mut(|s| {
+        s.incoming.retain(|key, _| !applied.contains_key(key));
+        s.incoming.extend(conflicting);
+    });
+    apply(applied);
+}
+
+/// Whether the draft of the other tab has to be chosen, as the draft of this one changed
+/// since `base`, the version the other tab changed, or the editor here shows the saved one.
+fn conflicts(key: &str, theirs: Option<&DraftEntry>, base: Option<DateTime<Utc>>) -> bool {
+    let mine = LocalStore::key_lang(key)
+        .and_then(|(resid, lang)| Dispatch::<LocalStore>::new().get().entry(&resid, lang));
+    match (mine, theirs) {
+        (Some(mine), Some(theirs)) if mine.data == theirs.data => false,
+        (Some(mine), _) => mine.modified != base,
+        (None, theirs) => theirs.is_some(),
+    }
+}
+
+fn apply(changes: BTreeMap<String, Option<DraftEntry>>) {
+    if changes.is_empty() {
+        return;
+    }
+    remember(changes.iter().map(|(k, e)| (k, e.as_ref())));
+    APPLYING.with(|a| a.set(true));
+    Dispatch::<LocalStore>::new().reduce_mut(|store| {
+        for (key, entry) in changes {
+            match entry {
+                Some(entry) => store.import(entry),
+                None => {
+                    if let Some((resid, lang)) = LocalStore::key_lang(&key) {
+                        store.remove(&resid, lang)
+                    }
+                }
+            }
+        }
+    });
+    APPLYING.with(|a| a.set(false));
+}
+
+/// Marks the draft open in the editor, keeping the drafts of this tab
+/// that were left while changed in another one.
+pub fn set_editing(key: Option<String>) {
+    let sync_dispatch = Dispatch::<DraftSync>::new();
+    let left = sync_dispatch
+        .get()
+        .incoming
+        .keys()
+        .filter(|k| Some(*k) != key.as_ref())
+        .cloned()
+        .collect::<Vec<_>>();
+    sync_dispatch.reduce_mut(|s| s.editing = key);
+    for key in left {
+        keep_mine(&key);
+    }
+}
+
+/// Replaces the draft with the one of the other tab.
+/// Returns `false` when that tab removed it.
+pub fn take_theirs(key: &str) -> bool {
+    let sync_dispatch = Dispatch::<DraftSync>::new();
+    let mut theirs = None;
+    sync_dispatch.reduce_mut(|s| theirs = s.incoming.remove(key));
+    let Some(theirs) = theirs else {
+        return true;
+    };
+    let exists = theirs.is_some();
+    apply(BTreeMap::from([(key.to_string(), theirs)]));
+    exists
+}
+
+/// Keeps the draft of this tab, storing it again and sending it to the other tabs.
+/// Without one here, the draft of the other tab is left to it.
+pub fn keep_mine(key: &str) {
+    let mut theirs = None;
+    Dispatch::<DraftSync>::new().reduce_mut(|s| theirs = s.incoming.remove(key));
+    let mine = LocalStore::key_lang(key)
+        .and_then(|(resid, lang)| Dispatch::<LocalStore>::new().get().entry(&resid, lang));
+    if mine.is_none() && matches!(theirs, Some(Some(_))) {
+        return;
+    }
+    let changes = BTreeMap::from([(key.to_string(), mine)]);
+    send(&changes);
+    drafts_db::queue(changes);
+}
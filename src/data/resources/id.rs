@@ -1,6 +1,9 @@
+use super::content::ContentType;
 use crate::api::{
+    backend::Backend,
     blob::BlobClient,
-    client::{ApiClient, RequestError},
+    client::RequestError,
+    container::{Blog, Container, ContentContainer, Project},
     resource::ResourceClient,
 };
 use petompp_web_models::models::country::Country;
@@ -36,7 +39,12 @@ impl TryInto<(ResId, Country)> for ResourceId {
                     match blob_type {
                         "blog" => ResId::Blob(BlobType::Blog(path.to_string())),
                         "prj" => ResId::Blob(BlobType::Project(path.to_string())),
-                        _ => return Err("invalid blob type".to_string()),
+                        kind => match ContentType::get(kind) {
+                            Some(c) => {
+                                ResId::Blob(BlobType::Content(c.key.to_string(), path.to_string()))
+                            }
+                            None => return Err("invalid blob type".to_string()),
+                        },
                     },
                     Country::try_from(lang).map_err(|_| "invalid lang")?,
                 ))
@@ -58,6 +66,7 @@ impl From<(ResId, Country)> for ResourceId {
                 blob: match blob_type {
                     BlobType::Blog(p) => Some(format!("blog@{}@{}", p, value.1.key())),
                     BlobType::Project(p) => Some(format!("prj@{}@{}", p, value.1.key())),
+                    BlobType::Content(kind, p) => Some(format!("{}@{}@{}", kind, p, value.1.key())),
                 },
             },
         }
@@ -68,6 +77,32 @@ impl From<(ResId, Country)> for ResourceId {
 pub enum BlobType {
     Blog(String),
     Project(String),
+    /// One of the [`ContentType`]s, by its key.
+    Content(String, String),
+}
+
+impl BlobType {
+    /// Fails for content of a type that isn't registered.
+    pub fn container(&self) -> Result<&'static str, RequestError> {
+        match self {
+            Self::Blog(_) => Ok(Blog::NAME),
+            Self::Project(_) => Ok(Project::NAME),
+            Self::Content(kind, _) => Ok(Self::content_type(kind)?.container),
+        }
+    }
+
+    pub fn content_path(&self, lang: Country) -> Result<String, RequestError> {
+        match self {
+            Self::Blog(id) => Ok(Blog::content_path(id, lang)),
+            Self::Project(id) => Ok(Project::content_path(id, lang)),
+            Self::Content(kind, id) => Ok(Self::content_type(kind)?.content_path(id, lang)),
+        }
+    }
+
+    fn content_type(kind: &str) -> Result<&'static ContentType, RequestError> {
+        ContentType::get(kind)
+            .ok_or_else(|| RequestError::Parse(format!("unknown content type {}", kind)))
+    }
 }
 
 impl Display for BlobType {
@@ -75,6 +110,7 @@ impl Display for BlobType {
         f.write_str(&match self {
             Self::Blog(id) => format!("blog:{}", id),
             Self::Project(id) => format!("prj:{}", id),
+            Self::Content(kind, id) => format!("{}:{}", kind, id),
         })
     }
 }
@@ -103,7 +139,13 @@ impl FromStr for ResId {
             "reskey" => Ok(Self::ResKey(id.to_string())),
             "blog" => Ok(Self::Blob(BlobType::Blog(id.to_string()))),
             "prj" => Ok(Self::Blob(BlobType::Project(id.to_string()))),
-            _ => Err("invalid id"),
+            kind => match ContentType::get(kind) {
+                Some(c) => Ok(Self::Blob(BlobType::Content(
+                    c.key.to_string(),
+                    id.to_string(),
+                ))),
+                None => Err("invalid id"),
+            },
         }
     }
 }
@@ -113,21 +155,19 @@ impl ResId {
         match self {
             Self::ResKey(id)
             | Self::Blob(BlobType::Blog(id))
-            | Self::Blob(BlobType::Project(id)) => id,
+            | Self::Blob(BlobType::Project(id))
+            | Self::Blob(BlobType::Content(_, id)) => id,
         }
     }
 
     pub async fn get_value(&self, lang: &Country) -> Result<String, RequestError> {
         match self {
-            Self::ResKey(reskey) => ApiClient::get_resource(reskey.as_str(), lang)
+            Self::ResKey(reskey) => Backend::get_resource(reskey.as_str(), lang)
                 .await
                 .map(|(_, v)| v),
             Self::Blob(blob_type) => {
-                let (container, filename) = match blob_type {
-                    BlobType::Blog(id) => ("blog", id),
-                    BlobType::Project(id) => ("project", id),
-                };
-                ApiClient::get_content_str(container, filename).await
+                Backend::get_content_str(blob_type.container()?, &blob_type.content_path(*lang)?)
+                    .await
             }
         }
     }
@@ -1,2 +1,6 @@
+pub mod content;
+pub mod drafts_db;
+pub mod drafts_sync;
 pub mod id;
+pub mod publication;
 pub mod store;
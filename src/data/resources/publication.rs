@@ -0,0 +1,154 @@
+use super::id::BlobType;
+use chrono::{DateTime, SecondsFormat, Utc};
+use petompp_web_models::models::{
+    blob::{blob_meta::BlobMetaData, blog::BlogMetaData, project::ProjectMetaData},
+    country::Country,
+    tag::{Tag, Tags},
+};
+
+/// Blob metadata entry holding the status, [`DRAFT`] or [`PUBLISHED`].
+/// Stored with the blob on the server, so everyone editing sees it, unlike the local drafts.
+pub const STATUS: &str = "status";
+pub const DRAFT: &str = "draft";
+pub const PUBLISHED: &str = "published";
+/// Blob metadata entry holding the time content gets public at, in RFC 3339.
+pub const PUBLISH_AT: &str = "publish_at";
+/// Tags that held the status before it had its own entries, still read when they are missing.
+pub const DRAFT_TAG: &str = "draft";
+pub const PUBLISH_AT_TAG: &str = "publish-at:";
+
+/// Metadata of content that can be held back from the public lists.
+pub trait Publishable {
+    fn blob(&self) -> &BlobMetaData;
+    fn blob_mut(&mut self) -> &mut BlobMetaData;
+
+    fn all_tags(&self) -> &Tags {
+        &self.blob().tags
+    }
+
+    fn all_tags_mut(&mut self) -> &mut Tags {
+        &mut self.blob_mut().tags
+    }
+
+    fn is_draft(&self) -> bool {
+        match self.blob().metadata.get(STATUS) {
+            Some(status) => status == DRAFT,
+            None => self.all_tags().tags().iter().any(|t| t.tag == DRAFT_TAG),
+        }
+    }
+
+    fn set_draft(&mut self, draft: bool) {
+        let publish_at = self.publish_at();
+        self.set_status(draft, publish_at);
+    }
+
+    fn publish_at(&self) -> Option<DateTime<Utc>> {
+        let date = match self.blob().metadata.get(STATUS) {
+            Some(_) => self.blob().metadata.get(PUBLISH_AT).cloned(),
+            None => self
+                .all_tags()
+                .tags()
+                .iter()
+                .find_map(|t| t.tag.strip_prefix(PUBLISH_AT_TAG).map(str::to_string)),
+        }?;
+        DateTime::parse_from_rfc3339(&date)
+            .ok()
+            .map(|d| d.with_timezone(&Utc))
+    }
+
+    /// Published, but waiting for its time.
+    fn is_scheduled(&self) -> bool {
+        !self.is_draft() && self.publish_at().is_some_and(|d| d > Utc::now())
+    }
+
+    /// Shown to everyone.
+    fn is_public(&self) -> bool {
+        !self.is_draft() && !self.is_scheduled()
+    }
+
+    /// Replaces the tags, keeping the status.
+    fn set_visible_tags(&mut self, tags: Tags) {
+        let (draft, publish_at) = (self.is_draft(), self.publish_at());
+        *self.all_tags_mut() = tags;
+        self.set_status(draft, publish_at);
+    }
+
+    fn set_status(&mut self, draft: bool, publish_at: Option<DateTime<Utc>>) {
+        let status = match draft {
+            true => DRAFT,
+            false => PUBLISHED,
+        };
+        let metadata = &mut self.blob_mut().metadata;
+        metadata.insert(STATUS.to_string(), status.to_string());
+        match publish_at {
+            Some(publish_at) => metadata.insert(
+                PUBLISH_AT.to_string(),
+                publish_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            ),
+            None => metadata.remove(PUBLISH_AT),
+        };
+        let tags = visible_tags(self.all_tags());
+        *self.all_tags_mut() = tags.into();
+    }
+}
+
+impl Publishable for BlobMetaData {
+    fn blob(&self) -> &BlobMetaData {
+        self
+    }
+
+    fn blob_mut(&mut self) -> &mut BlobMetaData {
+        self
+    }
+}
+
+impl Publishable for BlogMetaData {
+    fn blob(&self) -> &BlobMetaData {
+        self
+    }
+
+    fn blob_mut(&mut self) -> &mut BlobMetaData {
+        self
+    }
+}
+
+impl Publishable for ProjectMetaData {
+    fn blob(&self) -> &BlobMetaData {
+        self
+    }
+
+    fn blob_mut(&mut self) -> &mut BlobMetaData {
+        self
+    }
+}
+
+/// Tags without the ones holding the status.
+pub fn visible_tags(tags: &Tags) -> Vec<Tag> {
+    tags.tags()
+        .into_iter()
+        .filter(|t| t.tag != DRAFT_TAG && !t.tag.starts_with(PUBLISH_AT_TAG))
+        .collect()
+}
+
+/// Content in a single language, published at a set time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scheduled {
+    pub id: BlobType,
+    pub lang: Country,
+    pub title: String,
+    pub publish_at: DateTime<Utc>,
+}
+
+impl Scheduled {
+    pub fn new(meta: &impl Publishable, id: BlobType, lang: Country, title: &str) -> Option<Self> {
+        if !meta.is_scheduled() {
+            return None;
+        }
+        Some(Self {
+            id,
+            lang,
+            title: title.to_string(),
+            publish_at: meta.publish_at()?,
+        })
+    }
+}
@@ -1,17 +1,75 @@
 use crate::pages::editor::EditorData;
 
-use super::id::ResId;
-use petompp_web_models::models::blob::{blog::BlogMetaData, project::ProjectMetaData};
+use super::{drafts_db, drafts_sync, id::ResId, publication::Publishable};
+use crate::data::persist::{self, Migration, Versioned};
+use chrono::{DateTime, Utc};
+use petompp_web_models::models::{
+    blob::{blog::BlogMetaData, project::ProjectMetaData},
+    country::Country,
+};
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, str::FromStr};
-use yewdux::prelude::*;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+use yewdux::{listener::init_listener, prelude::*};
 
-#[derive(PartialEq, Clone, Debug, Store, Serialize, Deserialize, Default)]
-#[store(storage = "local")]
+/// Drafts of the editor, kept in IndexedDB by [`drafts_db`].
+/// Still deserializable to move the ones earlier versions kept in localStorage.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize, Default)]
 pub struct LocalStore {
     resources: BTreeMap<String, String>,
     blog_posts: BTreeMap<String, (String, BlogMetaData)>,
     projects: BTreeMap<String, (String, ProjectMetaData)>,
+    /// Saved version each draft started from, to notice it being saved again meanwhile.
+    #[serde(default)]
+    bases: BTreeMap<String, EditorData>,
+    #[serde(default)]
+    modified: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl Store for LocalStore {
+    fn new() -> Self {
+        init_listener(drafts_db::DraftsListener::default());
+        drafts_db::load();
+        drafts_sync::init();
+        Self::default()
+    }
+
+    fn should_notify(&self, old: &Self) -> bool {
+        self != old
+    }
+}
+
+/// Only read to move the drafts of earlier versions to [`drafts_db`].
+impl Versioned for LocalStore {
+    const MIGRATIONS: &'static [Migration] = &[persist::unversioned];
+}
+
+/// A cached draft, as listed on the drafts dashboard.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Draft {
+    pub resid: ResId,
+    pub lang: Country,
+    pub data: EditorData,
+    /// Unknown for drafts older than the dashboard.
+    pub modified: Option<DateTime<Utc>>,
+    /// Bytes of its json.
+    pub size: usize,
+}
+
+/// A draft with what it takes to continue it in another browser.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DraftEntry {
+    pub resid: ResId,
+    pub lang: String,
+    pub data: EditorData,
+    pub base: Option<EditorData>,
+    pub modified: Option<DateTime<Utc>>,
+}
+
+impl Versioned for DraftEntry {
+    const MIGRATIONS: &'static [Migration] = &[persist::unversioned];
 }
 
 impl LocalStore {
@@ -38,11 +96,96 @@ impl LocalStore {
             .collect()
     }
 
+    /// Keys of the drafts added, changed or removed since `old`.
+    pub(super) fn changed_since(&self, old: &Self) -> BTreeSet<String> {
+        fn changed<'a, V: PartialEq>(
+            new: &'a BTreeMap<String, V>,
+            old: &'a BTreeMap<String, V>,
+        ) -> impl Iterator<Item = &'a String> {
+            new.iter()
+                .filter(move |(k, v)| old.get(*k) != Some(*v))
+                .map(|(k, _)| k)
+                .chain(old.keys().filter(move |k| !new.contains_key(*k)))
+        }
+        changed(&self.resources, &old.resources)
+            .chain(changed(&self.blog_posts, &old.blog_posts))
+            .chain(changed(&self.projects, &old.projects))
+            .chain(changed(&self.bases, &old.bases))
+            .chain(changed(&self.modified, &old.modified))
+            .cloned()
+            .collect()
+    }
+
+    /// Number of drafts, without building them like [`Self::drafts`].
+    pub fn count(&self) -> usize {
+        self.resources.len() + self.blog_posts.len() + self.projects.len()
+    }
+
+    /// Every draft, the most recently changed first.
+    pub fn drafts(&self) -> Vec<Draft> {
+        let mut drafts = self
+            .resources
+            .keys()
+            .chain(self.blog_posts.keys())
+            .chain(self.projects.keys())
+            .filter_map(|k| {
+                let (resid, lang) = Self::key_lang(k)?;
+                let data = self.get(&resid, lang)?;
+                Some(Draft {
+                    size: serde_json::to_string(&data).map_or(0, |s| s.len()),
+                    modified: self.modified.get(k).copied(),
+                    lang: Country::try_from(lang).ok()?,
+                    resid,
+                    data,
+                })
+            })
+            .collect::<Vec<_>>();
+        drafts.sort_by(|a, b| b.modified.cmp(&a.modified));
+        drafts
+    }
+
+    pub fn entry(&self, key: &ResId, lang: &str) -> Option<DraftEntry> {
+        Some(DraftEntry {
+            data: self.get(key, lang)?,
+            base: self.base(key, lang).cloned(),
+            modified: self.modified.get(&Self::key(key, lang)).copied(),
+            resid: key.clone(),
+            lang: lang.to_string(),
+        })
+    }
+
+    /// Every draft with its base, by key.
+    pub fn entries(&self) -> BTreeMap<String, DraftEntry> {
+        self.resources
+            .keys()
+            .chain(self.blog_posts.keys())
+            .chain(self.projects.keys())
+            .filter_map(|k| {
+                let (resid, lang) = Self::key_lang(k)?;
+                Some((k.clone(), self.entry(&resid, lang)?))
+            })
+            .collect()
+    }
+
+    /// Adds the draft as it was exported, replacing the one kept here.
+    pub fn import(&mut self, entry: DraftEntry) {
+        let key = Self::key(&entry.resid, &entry.lang);
+        self.remove(&entry.resid, &entry.lang);
+        self.insert(entry.resid, &entry.lang, entry.data);
+        if let Some(base) = entry.base {
+            self.bases.insert(key.clone(), base);
+        }
+        if let Some(modified) = entry.modified {
+            self.modified.insert(key, modified);
+        }
+    }
+
     pub fn exists(&self, key: &ResId) -> bool {
         self.get_all_resids().iter().any(|x| x == key)
     }
 
     pub fn insert(&mut self, key: ResId, lang: &str, value: EditorData) {
+        self.modified.insert(Self::key(&key, lang), Utc::now());
         match value {
             EditorData::Resource(value) => {
                 self.resources.insert(Self::key(&key, lang), value);
@@ -56,22 +199,63 @@ impl LocalStore {
         }
     }
 
+    /// Inserts the draft, keeping `saved` as its base when the draft starts with this change.
+    pub fn insert_from(
+        &mut self,
+        key: ResId,
+        lang: &str,
+        value: EditorData,
+        saved: Option<&EditorData>,
+    ) {
+        if let (None, Some(saved)) = (self.get(&key, lang), saved) {
+            self.bases.insert(Self::key(&key, lang), saved.clone());
+        }
+        self.insert(key, lang, value);
+    }
+
+    pub fn base(&self, key: &ResId, lang: &str) -> Option<&EditorData> {
+        self.bases.get(&Self::key(key, lang))
+    }
+
+    /// Replaces the draft with one merged onto the newer saved version.
+    pub fn rebase(&mut self, key: ResId, lang: &str, value: EditorData, saved: EditorData) {
+        self.bases.insert(Self::key(&key, lang), saved);
+        self.insert(key, lang, value);
+    }
+
+    /// Makes the version just published the base of the draft,
+    /// giving the draft its status, so it shows it too.
+    pub fn set_published(&mut self, key: &ResId, lang: &str, saved: EditorData) {
+        let key = Self::key(key, lang);
+        let Some((draft, publish_at)) = saved.meta().map(|m| (m.is_draft(), m.publish_at())) else {
+            return;
+        };
+        if let Some((_, meta)) = self.blog_posts.get_mut(&key) {
+            meta.set_status(draft, publish_at);
+        }
+        if let Some((_, meta)) = self.projects.get_mut(&key) {
+            meta.set_status(draft, publish_at);
+        }
+        if let Some(base) = self.bases.get_mut(&key) {
+            *base = saved;
+        }
+    }
+
     pub fn remove(&mut self, key: &ResId, lang: &str) {
         let key = Self::key(key, lang);
         self.resources.remove(&key);
         self.blog_posts.remove(&key);
         self.projects.remove(&key);
+        self.bases.remove(&key);
+        self.modified.remove(&key);
     }
 
-    fn key(key: &ResId, lang: &str) -> String {
+    pub(crate) fn key(key: &ResId, lang: &str) -> String {
         key.to_string() + "." + lang
     }
 
-    fn key_lang(key: &str) -> Option<(ResId, &str)> {
-        let x = key
-            .split('.')
-            .fold((0usize, 0), |acc, x| (acc.0 + x.len() + 1, x.len()));
-        let x = x.0 - x.1 - 1 - 1;
-        Some((ResId::from_str(&key[..x]).ok()?, &key[x..]))
+    pub(crate) fn key_lang(key: &str) -> Option<(ResId, &str)> {
+        let (id, lang) = key.rsplit_once('.')?;
+        Some((ResId::from_str(id).ok()?, lang))
     }
 }
@@ -1,10 +1,35 @@
-use petompp_web_models::models::user::UserData;
+use super::persist::{self, Area, Migration, Persisted, Versioned};
+use petompp_web_models::models::user::{RoleData, UserData};
 use serde::{Deserialize, Serialize};
 use yewdux::prelude::*;
 
-#[derive(Default, PartialEq, Clone, Debug, Store, Serialize, Deserialize)]
-#[store(storage = "session", storage_tab_sync)]
+#[derive(Default, PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub struct SessionStore {
     pub user: Option<UserData>,
     pub token: Option<String>,
 }
+
+impl Store for SessionStore {
+    fn new() -> Self {
+        persist::init_tab_sync::<Self>();
+        persist::load()
+    }
+
+    fn should_notify(&self, old: &Self) -> bool {
+        self != old
+    }
+}
+
+impl Versioned for SessionStore {
+    const MIGRATIONS: &'static [Migration] = &[persist::unversioned];
+}
+
+impl Persisted for SessionStore {
+    const AREA: Area = Area::Session;
+}
+
+impl SessionStore {
+    pub fn is_admin(&self) -> bool {
+        self.user.as_ref().map(|u| &u.role) == Some(&RoleData::Admin)
+    }
+}
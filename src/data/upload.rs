@@ -0,0 +1,74 @@
+use crate::api::{abort::AbortScope, client::UploadProgress};
+use web_sys::File;
+use yewdux::prelude::*;
+
+/// Files dropped on or picked in the blob browser, uploaded a few at a time.
+#[derive(Default, PartialEq, Clone, Store)]
+pub struct UploadQueue {
+    items: Vec<QueuedUpload>,
+    next_id: usize,
+}
+
+#[derive(PartialEq, Clone)]
+pub struct QueuedUpload {
+    pub id: usize,
+    pub container: String,
+    /// Path relative to the folder it is uploaded to.
+    pub name: String,
+    pub file: File,
+    pub status: UploadStatus,
+    pub scope: AbortScope,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum UploadStatus {
+    Queued,
+    Uploading(UploadProgress),
+    /// Uploaded under the given name.
+    Done(String),
+    /// Not uploaded, a blob with the name already exists.
+    Skipped,
+    Cancelled,
+    Failed(String),
+}
+
+impl UploadStatus {
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, Self::Queued | Self::Uploading(_))
+    }
+}
+
+impl UploadQueue {
+    pub fn push(&mut self, container: &str, name: String, file: File) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.items.push(QueuedUpload {
+            id,
+            container: container.to_string(),
+            name,
+            file,
+            status: UploadStatus::Queued,
+            scope: AbortScope::default(),
+        });
+        id
+    }
+
+    pub fn get(&self, id: usize) -> Option<&QueuedUpload> {
+        self.items.iter().find(|i| i.id == id)
+    }
+
+    pub fn set_status(&mut self, id: usize, status: UploadStatus) {
+        if let Some(item) = self.items.iter_mut().find(|i| i.id == id) {
+            item.status = status;
+        }
+    }
+
+    pub fn items<'a>(&'a self, container: &'a str) -> impl Iterator<Item = &'a QueuedUpload> {
+        self.items.iter().filter(move |i| i.container == container)
+    }
+
+    pub fn clear_finished(&mut self, container: &str) {
+        self.items
+            .retain(|i| i.container != container || !i.status.is_finished());
+    }
+}
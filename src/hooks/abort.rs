@@ -0,0 +1,14 @@
+use crate::api::abort::AbortScope;
+use yew::prelude::*;
+
+/// Scope that lives as long as the component, requests spawned through it are
+/// aborted when the component unmounts.
+#[hook]
+pub fn use_abort_scope() -> AbortScope {
+    let scope = use_memo(|_| AbortScope::default(), ());
+    {
+        let scope = (*scope).clone();
+        use_effect_with_deps(move |_| move || scope.abort(), ());
+    }
+    (*scope).clone()
+}
@@ -0,0 +1,24 @@
+use crate::api::{blob::container_revision, cache::ResponseCache};
+use yew::prelude::*;
+
+/// Changes after every write to the container, for components showing its contents
+/// to fetch them again.
+#[hook]
+pub fn use_container_revision(container: &str) -> usize {
+    let revision = use_state_eq(|| container_revision(container));
+    {
+        let revision = revision.clone();
+        use_effect_with_deps(
+            move |container| {
+                let container = container.clone();
+                revision.set(container_revision(&container));
+                let id = ResponseCache::subscribe(Callback::from(move |_| {
+                    revision.set(container_revision(&container))
+                }));
+                move || ResponseCache::unsubscribe(id)
+            },
+            container.to_string(),
+        );
+    }
+    *revision
+}
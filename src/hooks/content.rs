@@ -0,0 +1,50 @@
+use crate::{
+    api::{
+        abort::AbortScope, backend::Backend, blob::BlobClient, client::RequestError,
+        container::content_path,
+    },
+    components::state::State,
+    data::locales::store::LocalesStore,
+};
+use petompp_web_models::models::blob::blob_meta::BlobMetaData;
+use serde::de::DeserializeOwned;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+/// Metadata and markdown of the content with the `id` in the current language,
+/// loaded again when either changes. `Ok(None)` until the first load starts.
+/// A load still running when the next one starts, or the component unmounts, is aborted,
+/// so a slower earlier response can't overwrite a newer one.
+#[hook]
+pub fn use_content<M>(
+    container: &'static str,
+    id: String,
+) -> State<Option<(M, String)>, RequestError>
+where
+    M: TryFrom<BlobMetaData> + DeserializeOwned + Clone + 'static,
+{
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let data = use_state(|| State::Ok(None));
+    {
+        let data = data.clone();
+        use_effect_with_deps(
+            move |(container, id, lang)| {
+                let (container, filename) = (*container, content_path(id, *lang));
+                data.set(State::Loading);
+                let load = AbortScope::default();
+                load.spawn(async move {
+                    match futures::join!(
+                        Backend::get_meta::<M>(container, &filename),
+                        Backend::get_content_str(container, &filename),
+                    ) {
+                        (Ok(meta), Ok(md)) => data.set(State::Ok(Some((meta, md)))),
+                        (Err(e), _) | (_, Err(e)) => data.set(State::Err(e)),
+                    }
+                });
+                move || load.abort()
+            },
+            (container, id, locales_store.curr),
+        );
+    }
+    (*data).clone()
+}
@@ -1,2 +1,5 @@
+pub mod abort;
 pub mod color_scheme;
+pub mod container;
+pub mod content;
 pub mod event;
@@ -1,10 +1,13 @@
 use crate::{
-    api::client::LocalClient,
+    api::{client::LocalClient, memory::MemoryClient},
     components::{
         atoms::modal::{ErrorModal, Modal},
-        organisms::header::Header,
+        organisms::{data_recovery::DataRecovery, header::Header},
+    },
+    data::{
+        config::{BackendKind, Config, ConfigStore},
+        locales::store::LocalesStore,
     },
-    data::locales::store::LocalesStore,
     router::route::Route,
 };
 use yew::{platform::spawn_local, prelude::*};
@@ -49,7 +52,37 @@ pub fn app_base(props: &AppProps) -> Html {
 #[function_component(App)]
 pub fn app() -> Html {
     let error_state = use_state(|| None);
+    let (config_store, config_dispatch) = use_store::<ConfigStore>();
     let (locale_store, locale_dispatch) = use_store::<LocalesStore>();
+    {
+        let locale_dispatch = locale_dispatch.clone();
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    let config = match LocalClient::get_config().await {
+                        Ok(config) => config,
+                        Err(e) => {
+                            gloo::console::warn!(format!("Using compiled config: {}", e));
+                            Config::default()
+                        }
+                    };
+                    if config.backend == BackendKind::Memory {
+                        match LocalClient::get_fixtures(&config.fixtures()).await {
+                            Ok(fixtures) => MemoryClient::seed(fixtures),
+                            Err(e) => gloo::console::warn!(format!("No demo fixtures: {}", e)),
+                        }
+                    }
+                    if let Some(lang) = config.default_lang() {
+                        if !LocalesStore::is_persisted() {
+                            locale_dispatch.reduce_mut(|l| l.curr = lang);
+                        }
+                    }
+                    config_dispatch.reduce_mut(|c| c.load(config));
+                })
+            },
+            (),
+        );
+    }
     if !locale_store.is_loaded(locale_store.curr) || error_state.is_some() {
         let locale_store = locale_store.clone();
         let locale_dispatch = locale_dispatch.clone();
@@ -83,6 +116,10 @@ pub fn app() -> Html {
         },
         locale_store.curr,
     );
+    // API and storage URLs come from the config
+    if !config_store.is_loaded() {
+        return html! {};
+    }
 
     html! {
         <BrowserRouter>
@@ -91,6 +128,7 @@ pub fn app() -> Html {
             </AppBase>
             <Modal />
             <ErrorModal />
+            <DataRecovery />
         </BrowserRouter>
     }
 }
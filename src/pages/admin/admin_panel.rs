@@ -1,4 +1,5 @@
 use crate::{
+    components::organisms::admin::upcoming::Upcoming,
     data::{resources::id::ResId, session::SessionStore},
     pages::{not_found::NotFound, page_base::EditablePage},
 };
@@ -14,6 +15,8 @@ pub fn admin_panel() -> Html {
         return html! {<NotFound />};
     };
     html! {
-        <EditablePage title={"Admin panel".to_string()} resid={ResId::ResKey(RES_KEY.to_string())}/ >
+        <EditablePage title={"Admin panel".to_string()} resid={ResId::ResKey(RES_KEY.to_string())}>
+            <Upcoming />
+        </EditablePage>
     }
 }
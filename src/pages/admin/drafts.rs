@@ -0,0 +1,24 @@
+use crate::{
+    components::organisms::editor::draft_list::DraftList,
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        session::SessionStore,
+    },
+    pages::{not_found::NotFound, page_base::PageBase},
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[function_component(Drafts)]
+pub fn drafts() -> Html {
+    let (session_store, _) = use_store::<SessionStore>();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    if !session_store.is_admin() {
+        return html! {<NotFound />};
+    }
+    html! {
+        <PageBase title={locales_store.get(TK::Drafts)}>
+            <DraftList />
+        </PageBase>
+    }
+}
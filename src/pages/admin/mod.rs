@@ -1,2 +1,3 @@
 pub mod admin_panel;
+pub mod drafts;
 pub mod user_management;
@@ -1,17 +1,20 @@
+use crate::api::backend::Backend;
 use crate::api::blob::BlobClient;
-use crate::api::client::ApiClient;
+use crate::api::container::{self, Container};
 use crate::components::atoms::loading::Loading;
 use crate::components::state::State;
 use crate::data::locales::store::LocalesStore;
 use crate::data::locales::tk::TK;
 use crate::data::resources::id::ResId;
+use crate::data::resources::publication::Publishable;
+use crate::data::session::SessionStore;
+use crate::hooks::abort::use_abort_scope;
 use crate::{
     components::{atoms::markdown::Editable, organisms::blog::blog_summary::BlogSummary},
     pages::page_base::PageBase,
 };
 use petompp_web_models::models::blob::blog::BlogMetaData;
 use petompp_web_models::models::tag::Tags;
-use yew::platform::spawn_local;
 use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::*;
@@ -21,7 +24,10 @@ pub fn blog() -> Html {
     let location = use_location().unwrap();
     let tags = location.query::<Tags>().unwrap_or_default();
     let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, _) = use_store::<SessionStore>();
+    let is_admin = session_store.is_admin();
     let data = use_state(|| State::Ok(None));
+    let abort = use_abort_scope();
     use_effect_with_deps(
         move |data| {
             let data = data.clone();
@@ -29,8 +35,8 @@ pub fn blog() -> Html {
                 State::Loading | State::Err(_) | State::Ok(Some(_)) => return,
                 _ => data.set(State::Loading),
             }
-            spawn_local(async move {
-                match ApiClient::get_meta_all::<BlogMetaData>("blog", None).await {
+            abort.spawn(async move {
+                match Backend::get_meta_all::<BlogMetaData>(container::Blog::NAME, None).await {
                     Ok(posts) => data.set(State::Ok(Some(posts))),
                     Err(e) => data.set(State::Err(e)),
                 };
@@ -47,6 +53,7 @@ pub fn blog() -> Html {
                     tags.is_empty() || tags.tags().iter().any(|t| meta.tags.tags().contains(t))
                 })
                 .filter(|meta| meta.lang() == locales_store.curr)
+                .filter(|meta| is_admin || meta.is_public())
                 .map(|meta| {
                     html! {
                         <BlogSummary {meta}/>
@@ -1,23 +1,31 @@
 use crate::{
-    api::{blob::BlobClient, client::ApiClient},
+    api::{
+        client::RequestError,
+        container::{Blog, Container, ImageUpload},
+    },
     components::{
         atoms::{
             loading::Loading,
             markdown::{EditButton, Markdown},
+            publication_badge::PublicationBadge,
         },
         state::State,
     },
     data::{
         locales::{store::LocalesStore, tk::TK},
-        resources::id::{BlobType, ResId},
+        resources::{
+            id::{BlobType, ResId},
+            publication::Publishable,
+        },
         session::SessionStore,
     },
-    pages::page_base::PageBase,
+    hooks::content::use_content,
+    pages::{not_found::NotFound, page_base::PageBase},
     router::route::Route,
 };
 use chrono::{DateTime, Local};
-use petompp_web_models::models::blob::{blog::BlogMetaData, markdown::MarkdownMeta};
-use yew::{platform::spawn_local, prelude::*};
+use petompp_web_models::models::blob::blog::BlogMetaData;
+use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::*;
 
@@ -28,43 +36,32 @@ pub struct BlogPostProps {
 
 #[function_component(BlogPost)]
 pub fn blog_post(props: &BlogPostProps) -> Html {
+    html! {
+        <BlogPostContent
+            container={Blog::NAME}
+            blob={BlobType::Blog(props.id.clone())}
+            back={(TK::BackToBlogPosts, Route::Blog)}/>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct BlogPostContentProps {
+    pub container: &'static str,
+    pub blob: BlobType,
+    /// Text and target of the link back to the list.
+    pub back: (TK, Route),
+}
+
+/// Page of a blog post, or of a piece of a content type shown like one.
+#[function_component(BlogPostContent)]
+pub fn blog_post_content(props: &BlogPostContentProps) -> Html {
     let navigator = use_navigator().unwrap();
     let (_, session_dispatch) = use_store::<SessionStore>();
     let (locales_store, _) = use_store::<LocalesStore>();
-    let data = use_state(|| State::Ok(None));
-    use_effect_with_deps(
-        |(props, data, locales_store)| {
-            let props = props.clone();
-            let data = data.clone();
-            let locales_store = locales_store.clone();
-            match *data {
-                State::Ok(Some((_, _, last))) if last == locales_store.curr => return,
-                State::Loading | State::Err(_) => return,
-                _ => data.set(State::Loading),
-            }
-            spawn_local(async move {
-                let filename = MarkdownMeta::create_filename(props.id.as_str(), locales_store.curr);
-                let meta = match ApiClient::get_meta::<BlogMetaData>("blog", &filename).await {
-                    Ok(meta) => meta,
-                    Err(e) => {
-                        data.set(State::Err(e));
-                        return;
-                    }
-                };
-                let md = match ApiClient::get_content_str("blog", &filename).await {
-                    Ok(content) => content,
-                    Err(e) => {
-                        data.set(State::Err(e));
-                        return;
-                    }
-                };
-                data.set(State::Ok(Some((meta, md, locales_store.curr))));
-            });
-        },
-        (props.clone(), data.clone(), locales_store.clone()),
-    );
-    let (meta, markdown, title) = match &*data {
-        State::Ok(Some((m, md, _))) => (
+    let resid = ResId::Blob(props.blob.clone());
+    let data = use_content::<BlogMetaData>(props.container, resid.id().to_string());
+    let (meta, markdown, title) = match &data {
+        State::Ok(Some((m, md))) => (
             html! {<BlogPostMeta meta={m.clone()} />},
             Some(html! {<Markdown markdown={md.clone()} allowhtml={true} interactive={Some(())}/>}),
             m.title().clone(),
@@ -72,27 +69,27 @@ pub fn blog_post(props: &BlogPostProps) -> Html {
         State::Loading | State::Ok(None) => {
             (html! { <Loading /> }, None, locales_store.get(TK::Loading))
         }
+        // content waiting for its publish date isn't found either
+        State::Err(RequestError::Endpoint(404, _)) => return html! { <NotFound /> },
         State::Err(e) => {
             if let Err(redirect) = e.handle_failed_auth(session_dispatch) {
                 return redirect;
             }
             (
                 html! {
-                    <>
                     <h3 class={"mx-auto py-4 text-xl font-semibold text-error"}>{e.to_string()}</h3>
-                    <p>{e.to_string()}</p>
-                    </>
                 },
                 None,
                 locales_store.get(TK::ErrorOccured),
             )
         }
     };
-    let onclick = Callback::from(move |_| navigator.push(&Route::Blog));
+    let (back, route) = props.back.clone();
+    let onclick = Callback::from(move |_| navigator.push(&route));
     html! {
         <PageBase {title}>
-            <EditButton resid={ResId::Blob(BlobType::Blog(props.id.clone()))} />
-            <a class={"lg:mb-6 mb-4"} href={"javascript:void(0);"} {onclick}>{locales_store.get(TK::BackToBlogPosts)}</a>
+            <EditButton {resid} />
+            <a class={"lg:mb-6 mb-4"} href={"javascript:void(0);"} {onclick}>{locales_store.get(back)}</a>
             {meta}
             <div class={"divider"}/>
             <div class={"mx-auto flex flex-col w-full"}>
@@ -111,7 +108,7 @@ pub struct BlogPostMetaProps {
 pub fn blog_post_meta(props: &BlogPostMetaProps) -> Html {
     let img = match props.meta.image().as_str() {
         "" => "/img/placeholder.svg".to_string(),
-        img => <ApiClient as BlobClient>::get_url("image-upload", img),
+        img => ImageUpload::url(img),
     };
     let sign = format!(
         "{} by {}",
@@ -129,7 +126,10 @@ pub fn blog_post_meta(props: &BlogPostMetaProps) -> Html {
                 </div>
             </div>
         </div>
-        <p class={"italic text-lg"}>{sign}</p>
+        <div class={"flex flex-row gap-2 items-center"}>
+            <p class={"italic text-lg"}>{sign}</p>
+            <PublicationBadge draft={props.meta.is_draft()} publish_at={props.meta.publish_at()}/>
+        </div>
         </>
     }
 }
@@ -0,0 +1,146 @@
+use crate::{
+    api::{backend::Backend, editor::EditorClient},
+    components::{
+        atoms::{link::RouteLink, loading::Loading, publication_badge::PublicationBadge},
+        organisms::blog::blog_summary::BlogSummary,
+        state::State,
+    },
+    data::{
+        locales::{store::LocalesStore, tk::TK},
+        resources::{
+            content::{ContentMeta, ContentType, MetaKind},
+            id::BlobType,
+            publication::Publishable,
+        },
+        session::SessionStore,
+    },
+    hooks::abort::use_abort_scope,
+    pages::{
+        blog_post::BlogPostContent, not_found::NotFound, page_base::PageBase,
+        project::ProjectContent,
+    },
+    router::route::Route,
+};
+use petompp_web_models::models::tag::Tags;
+use yew::prelude::*;
+use yew_router::prelude::*;
+use yewdux::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct ContentListProps {
+    pub kind: String,
+}
+
+#[function_component(ContentList)]
+pub fn content_list(props: &ContentListProps) -> Html {
+    let location = use_location().unwrap();
+    let tags = location.query::<Tags>().unwrap_or_default();
+    let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, _) = use_store::<SessionStore>();
+    let is_admin = session_store.is_admin();
+    let data = use_state(|| State::Ok(None));
+    let abort = use_abort_scope();
+    let content_type = ContentType::get_enabled(&props.kind);
+    {
+        let data = data.clone();
+        use_effect_with_deps(
+            move |content_type| {
+                let Some(content_type) = *content_type else {
+                    return;
+                };
+                data.set(State::Loading);
+                abort.spawn(async move {
+                    match Backend::get_content_metas(content_type).await {
+                        Ok(items) => data.set(State::Ok(Some(items))),
+                        Err(e) => data.set(State::Err(e)),
+                    };
+                });
+            },
+            content_type,
+        );
+    }
+    let Some(content_type) = content_type else {
+        return html! { <NotFound /> };
+    };
+    let items = match &*data {
+        State::Ok(Some(items)) => {
+            let summaries = items
+                .clone()
+                .into_iter()
+                .filter(|meta| {
+                    tags.is_empty()
+                        || tags
+                            .tags()
+                            .iter()
+                            .any(|t| meta.all_tags().tags().contains(t))
+                })
+                .filter(|meta| meta.lang() == locales_store.curr)
+                .filter(|meta| is_admin || meta.is_public())
+                .map(|meta| match meta {
+                    ContentMeta::Blog(meta) => html! {
+                        <BlogSummary {meta} kind={props.kind.clone()}/>
+                    },
+                    ContentMeta::Project(meta) => {
+                        let route = Route::ContentItem {
+                            kind: props.kind.clone(),
+                            id: meta.id().to_string(),
+                        };
+                        html! {
+                            <li class={"list-none"}>
+                                <RouteLink {route} text={meta.title().clone()}/>
+                                <PublicationBadge class={"badge-sm not-prose ml-2"} draft={meta.is_draft()} publish_at={meta.publish_at()}/>
+                            </li>
+                        }
+                    }
+                });
+
+            html! {
+                <div class={"flex flex-col gap-2"}>
+                    {for summaries}
+                </div>
+            }
+        }
+        State::Loading | State::Ok(None) => html! {
+            <Loading resource={locales_store.get(TK::Label(content_type.title))} />
+        },
+        State::Err(e) => {
+            html! {
+                <>
+                <h3 class={"mx-auto py-4 text-xl font-semibold"}>{locales_store.get(TK::ErrorOccured)}</h3>
+                <p>{e.to_string()}</p>
+                </>
+            }
+        }
+    };
+    html! {
+        <PageBase title={locales_store.get(TK::Label(content_type.title))}>
+        {items}
+        </PageBase>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct ContentItemProps {
+    pub kind: String,
+    pub id: String,
+}
+
+#[function_component(ContentItem)]
+pub fn content_item(props: &ContentItemProps) -> Html {
+    let Some(content_type) = ContentType::get_enabled(&props.kind) else {
+        return html! { <NotFound /> };
+    };
+    let container = content_type.container;
+    let blob = BlobType::Content(props.kind.clone(), props.id.clone());
+    let list = Route::ContentList {
+        kind: props.kind.clone(),
+    };
+    match content_type.meta {
+        MetaKind::Blog => html! {
+            <BlogPostContent {container} {blob} back={(TK::Label(content_type.back), list)}/>
+        },
+        MetaKind::Project => html! {
+            <ProjectContent {container} {blob} {list} back={Some(TK::Label(content_type.back))}/>
+        },
+    }
+}
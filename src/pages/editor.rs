@@ -1,18 +1,25 @@
 use crate::{
     api::{
-        client::{ApiClient, RequestError},
+        backend::Backend,
+        client::RequestError,
+        container::{Container, Project},
         editor::EditorClient,
         resource::ResourceClient,
     },
     components::{
         atoms::{
-            collapse::Collapse, loading::Loading, markdown::Editable,
-            resource_select::ResourceSelect,
+            collapse::Collapse, date_display::Countdown, loading::Loading, markdown::Editable,
+            publication_badge::PublicationBadge, resource_select::ResourceSelect,
         },
         organisms::{
             blog::blog_meta_editor::BlogMetaEditor,
             editor::atoms::{
-                delete_button::DeleteButton, discard_button::DiscardButton, save_button::SaveButton,
+                delete_button::DeleteButton, discard_button::DiscardButton,
+                publish_button::PublishButton, save_button::SaveButton,
+            },
+            editor::{
+                draft_storage::DraftStorageWarning, draft_sync_banner::DraftSyncBanner,
+                revision_history::RevisionHistory,
             },
             markdown::markdown_editor::MarkdownEditor,
             markdown::markdown_preview::MarkdownPreview,
@@ -23,17 +30,22 @@ use crate::{
     data::{
         locales::{store::LocalesStore, tk::TK},
         resources::{
+            content::{ContentType, MetaKind},
             id::{BlobType, ResId, ResourceId},
+            publication::Publishable,
             store::LocalStore,
         },
         session::SessionStore,
     },
     pages::page_base::PageBase,
-    router::route::Route,
+    router::{admin::AdminRoute, route::Route},
     utils::style::get_svg_bg_mask_style,
 };
-use petompp_web_models::models::blob::{blog::BlogMetaData, project::ProjectMetaData};
+use petompp_web_models::models::blob::{
+    blob_meta::BlobMetaData, blog::BlogMetaData, project::ProjectMetaData,
+};
 use petompp_web_models::models::country::Country;
+use serde::{Deserialize, Serialize};
 use web_sys::HtmlInputElement;
 use yew::platform::spawn_local;
 use yew::prelude::*;
@@ -42,10 +54,12 @@ use yewdux::prelude::*;
 
 pub type EditorState = State<Option<EditorDataState>, RequestError>;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EditorData {
     Resource(String),
+    /// Blog posts and the content types with blog post metadata.
     Blog((String, BlogMetaData)),
+    /// Projects and the content types with project metadata.
     Project((String, ProjectMetaData)),
 }
 
@@ -67,6 +81,29 @@ impl EditorData {
             Self::Project((_, m)) => Self::Project((s, m)),
         }
     }
+
+    /// Whether it is the kind of data kept for `resid`.
+    pub fn fits(&self, resid: &ResId) -> bool {
+        match (resid, self) {
+            (ResId::ResKey(_), Self::Resource(_))
+            | (ResId::Blob(BlobType::Blog(_)), Self::Blog(_))
+            | (ResId::Blob(BlobType::Project(_)), Self::Project(_)) => true,
+            (ResId::Blob(BlobType::Content(kind, _)), data) => matches!(
+                (ContentType::get(kind).map(|c| c.meta), data),
+                (Some(MetaKind::Blog), Self::Blog(_)) | (Some(MetaKind::Project), Self::Project(_))
+            ),
+            _ => false,
+        }
+    }
+
+    /// Metadata of the blob, resources have none.
+    pub fn meta(&self) -> Option<&BlobMetaData> {
+        match self {
+            Self::Resource(_) => None,
+            Self::Blog((_, m)) => Some(m.blob()),
+            Self::Project((_, m)) => Some(m.blob()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -76,6 +113,16 @@ pub struct EditorDataState {
     pub data: EditorData,
 }
 
+impl EditorDataState {
+    /// What a draft of an already saved resource starts from, while there is no draft yet.
+    pub fn saved(&self) -> Option<&EditorData> {
+        match self.is_new {
+            Some(false) => Some(&self.data),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Properties)]
 pub struct EditorProps {
     pub state: EditorState,
@@ -140,7 +187,7 @@ pub fn editor() -> Html {
             spawn_local(async move {
                 // does current resource exist?
                 let (is_new, new_state) = match &resid {
-                    ResId::Blob(blob_type) => match ApiClient::get_data(blob_type, lang).await {
+                    ResId::Blob(blob_type) => match Backend::get_data(blob_type, lang).await {
                         Ok(ed) => (ed.is_none(), ed),
                         Err(e) => match cached_state.is_some() || any_cached_state {
                             true => (true, None),
@@ -150,7 +197,7 @@ pub fn editor() -> Html {
                             }
                         },
                     },
-                    ResId::ResKey(k) => match ApiClient::get_resource(k.as_str(), &lang).await {
+                    ResId::ResKey(k) => match Backend::get_resource(k.as_str(), &lang).await {
                         Ok((c, v)) => match c == lang {
                             true => (false, Some(EditorData::Resource(v))),
                             false => (false, None),
@@ -188,15 +235,28 @@ pub fn editor() -> Html {
                     }
                     (None, None) => {
                         let data = match &resid {
-                            ResId::Blob(blob_type) => match blob_type {
-                                BlobType::Blog(id) => {
-                                    EditorData::Blog((String::new(), BlogMetaData::empty(id, lang)))
+                            ResId::Blob(blob_type) => {
+                                let (id, kind) = match blob_type {
+                                    BlobType::Blog(id) => (id, MetaKind::Blog),
+                                    BlobType::Project(id) => (id, MetaKind::Project),
+                                    BlobType::Content(kind, id) => (
+                                        id,
+                                        ContentType::get(kind).map_or(MetaKind::Blog, |c| c.meta),
+                                    ),
+                                };
+                                match kind {
+                                    MetaKind::Blog => {
+                                        let mut meta = BlogMetaData::empty(id, lang);
+                                        meta.set_draft(true);
+                                        EditorData::Blog((String::new(), meta))
+                                    }
+                                    MetaKind::Project => {
+                                        let mut meta = ProjectMetaData::empty(id, lang);
+                                        meta.set_draft(true);
+                                        EditorData::Project((String::new(), meta))
+                                    }
                                 }
-                                BlobType::Project(id) => EditorData::Project((
-                                    String::new(),
-                                    ProjectMetaData::empty(id, lang),
-                                )),
-                            },
+                            }
                             ResId::ResKey(_) => EditorData::Resource(String::new()),
                         };
                         state.set(State::Ok(Some(EditorDataState {
@@ -218,6 +278,7 @@ pub fn editor() -> Html {
     let (editor, title) = match &*state {
         State::Ok(Some(state)) => {
             let (resid, lang) = state.id.clone();
+            let saved = state.saved().cloned();
             let editor = match &*is_preview {
                 true => {
                     html! {<MarkdownPreview data={state.clone()} />}
@@ -228,8 +289,9 @@ pub fn editor() -> Html {
                         let resid = resid.clone();
                         let lang = lang;
                         Callback::from(move |data: EditorData| {
-                            local_dispatch
-                                .reduce_mut(|store| store.insert(resid.clone(), lang.key(), data));
+                            local_dispatch.reduce_mut(|store| {
+                                store.insert_from(resid.clone(), lang.key(), data, saved.as_ref())
+                            });
                         })
                     };
                     html! {
@@ -297,6 +359,19 @@ pub fn editor() -> Html {
             })
         }
     };
+    let drafts = {
+        let count = local_store.count();
+        let navigator = navigator.clone();
+        let onclick = Callback::from(move |_| navigator.push(&AdminRoute::Drafts));
+        (count > 0).then(|| {
+            html! {
+                <button class={"btn"} {onclick}>
+                    {locales_store.get(TK::Drafts)}
+                    <span class={"badge badge-secondary"}>{count}</span>
+                </button>
+            }
+        })
+    };
     let onselectedchanged = {
         let navigator = navigator.clone();
         Callback::from(move |resource_id| {
@@ -313,14 +388,16 @@ pub fn editor() -> Html {
         State::Ok(Some(state)) => {
             let local_dispatch = local_dispatch.clone();
             let (resid, lang) = state.id.clone();
+            let saved = state.saved().cloned();
             match state.data.clone() {
                 EditorData::Blog((value, meta)) => {
                     let ondatachanged = Callback::from(move |new_data: BlogMetaData| {
                         local_dispatch.reduce_mut(|store| {
-                            store.insert(
+                            store.insert_from(
                                 resid.clone(),
                                 lang.key(),
                                 EditorData::Blog((value.clone(), new_data)),
+                                saved.as_ref(),
                             )
                         })
                     });
@@ -331,18 +408,25 @@ pub fn editor() -> Html {
                     })
                 }
                 EditorData::Project((value, meta)) => {
+                    // projects and the content types shown like them
+                    let container = match &resid {
+                        ResId::Blob(blob) => blob.container().ok(),
+                        ResId::ResKey(_) => None,
+                    }
+                    .unwrap_or(Project::NAME);
                     let ondatachanged = Callback::from(move |new_data: ProjectMetaData| {
                         local_dispatch.reduce_mut(|store| {
-                            store.insert(
+                            store.insert_from(
                                 resid.clone(),
                                 lang.key(),
                                 EditorData::Project((value.clone(), new_data)),
+                                saved.as_ref(),
                             )
                         })
                     });
                     Some(html! {
                         <Collapse label={locales_store.get(TK::ProjectMetadata)}>
-                            <ProjectMetaEditor data={meta.clone()} {ondatachanged} />
+                            <ProjectMetaEditor data={meta.clone()} {container} {ondatachanged} />
                         </Collapse>
                     })
                 }
@@ -358,12 +442,36 @@ pub fn editor() -> Html {
         State::Ok(Some(s)) => s.is_new,
         _ => None,
     };
+    let history = match &*state {
+        State::Ok(Some(state)) if !state.is_new.unwrap_or_default() => {
+            let (resid, lang) = state.id.clone();
+            let onrestore = {
+                let local_dispatch = local_dispatch.clone();
+                let resid = resid.clone();
+                let saved = state.saved().cloned();
+                Callback::from(move |data: EditorData| {
+                    local_dispatch.reduce_mut(|store| {
+                        store.insert_from(resid.clone(), lang.key(), data, saved.as_ref())
+                    });
+                })
+            };
+            Some(html! {
+                <Collapse label={locales_store.get(TK::Revisions)}>
+                    <RevisionHistory {resid} {lang} current={state.data.clone()} {onrestore}/>
+                </Collapse>
+            })
+        }
+        _ => None,
+    };
     let edit_text = {
         let edit_pref = match is_new {
             Some(true) => locales_store.get(TK::Creating),
             _ => locales_store.get(TK::Editing),
         };
         match match &resid {
+            Some(ResId::Blob(BlobType::Content(kind, _))) => {
+                ContentType::get(kind).map(|c| locales_store.get(TK::Label(c.item)))
+            }
             Some(ResId::Blob(_)) => Some(locales_store.get(TK::BlogPost)),
             Some(ResId::ResKey(_)) => Some(locales_store.get(TK::Resource)),
             None => None,
@@ -372,6 +480,24 @@ pub fn editor() -> Html {
             None => format!("{}:", edit_pref),
         }
     };
+    let status = match &*state {
+        State::Ok(Some(s)) => match &s.data {
+            EditorData::Blog((_, meta)) => Some((meta.is_draft(), meta.publish_at())),
+            EditorData::Project((_, meta)) => Some((meta.is_draft(), meta.publish_at())),
+            EditorData::Resource(_) => None,
+        },
+        _ => None,
+    }
+    .map(|(draft, publish_at)| {
+        html! {
+            <>
+            <PublicationBadge {draft} {publish_at}/>
+            if let (false, Some(date)) = (draft, publish_at) {
+                <Countdown {date}/>
+            }
+            </>
+        }
+    });
     let onchange = Callback::from(move |e: Event| {
         let element: HtmlInputElement = e.target_unchecked_into();
         is_preview.set(element.checked());
@@ -399,17 +525,23 @@ pub fn editor() -> Html {
             <Editable resid={ResId::ResKey("editor-intro".to_string())}/>
             <div class={"flex flex-col lg:flex-row gap-4 pb-6 items-center"}>
                 <h2 class={"flex font-semibold text-2xl"}>{edit_text}</h2>
+                {status}
                 <ResourceSelect resid={resid.clone()} lang={lang} {onselectedchanged} state={Some((*state).clone())}/>
                 <div class={"flex flex-row flex-wrap gap-4 lg:w-auto w-full"}>
                     {go_back}
                     {reload}
+                    {drafts}
                     <DiscardButton state={(*state).clone()} onstatechanged={onstatechanged.clone()} resid={resid.clone()} lang={lang}/>
                     <SaveButton state={(*state).clone()} onstatechanged={onstatechanged.clone()} resid={resid.clone()} lang={lang}/>
-                    <DeleteButton state={(*state).clone()} {onstatechanged} resid={resid.clone()} lang={lang}/>
+                    <PublishButton state={(*state).clone()} onstatechanged={onstatechanged.clone()} resid={resid.clone()} lang={lang}/>
+                    <DeleteButton state={(*state).clone()} onstatechanged={onstatechanged.clone()} resid={resid.clone()} lang={lang}/>
                 </div>
             </div>
             <div class={"flex flex-col gap-6"}>
+                <DraftSyncBanner {resid} {lang} {onstatechanged}/>
+                <DraftStorageWarning />
                 {meta_editor}
+                {history}
                 <div class={"flex flex-row gap-4"}>
                     <p>{locales_store.get(TK::Editor)}</p>
                     <input type={"checkbox"} class={"toggle bg-base-content hover:bg-base-content"} {onchange}/>
@@ -1,5 +1,5 @@
 use crate::{
-    api::{self, client::ApiClient, user::UserClient},
+    api::{self, backend::Backend, user::UserClient},
     async_event,
     components::atoms::{
         modal::show_error,
@@ -63,7 +63,7 @@ pub fn login() -> Html {
         async_event!(
             [prevent SubmitEvent] |form_data, history, session_dispatch, locales_store, returnto| {
                 let creds = form_data.borrow().clone();
-                match ApiClient::login(creds).await {
+                match Backend::login(creds).await {
                     Ok(response) => {
                         session_dispatch.reduce(|_| {
                             SessionStore {
@@ -84,6 +84,9 @@ pub fn login() -> Html {
                         api::client::RequestError::Parse(message) | api::client::RequestError::Network(message) => {
                             show_error(message, Some((&Route::Home, &history)))
                         }
+                        api::client::RequestError::Timeout => {
+                            show_error(locales_store.get(TK::RequestTimedOut), Some((&Route::Home, &history)))
+                        }
                     },
                 }
             }
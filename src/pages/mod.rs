@@ -3,6 +3,7 @@ pub mod admin;
 pub mod blog;
 pub mod blog_post;
 pub mod contact;
+pub mod content;
 pub mod editor;
 pub mod home;
 pub mod login;
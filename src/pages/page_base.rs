@@ -70,6 +70,7 @@ pub fn editable_page_base(props: &EditablePageBaseProps) -> Html {
     html! {
         <PageBase title={props.title.clone()}>
             <Editable resid={props.resid.clone()}/ >
+            {props.children.clone()}
         </PageBase>
     }
 }
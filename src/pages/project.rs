@@ -1,26 +1,36 @@
 use crate::{
     api::{
+        asset::AssetKind,
+        backend::Backend,
         blob::BlobClient,
-        client::{ApiClient, RequestError},
+        client::RequestError,
+        container::{self, Container},
+        image::{is_thumbnail, thumbnail_path},
     },
     components::{
         atoms::{
+            asset_preview::AssetPreview,
             carousel::{Carousel, Slide},
             loading::Loading,
             markdown::{EditButton, Markdown},
+            publication_badge::PublicationBadge,
         },
         state::State,
     },
     data::{
         locales::{store::LocalesStore, tk::TK},
-        resources::id::{BlobType, ResId},
+        resources::{
+            id::{BlobType, ResId},
+            publication::Publishable,
+        },
         session::SessionStore,
     },
+    hooks::{abort::use_abort_scope, content::use_content},
     pages::page_base::PageBase,
     router::route::Route,
 };
-use petompp_web_models::models::blob::{markdown::MarkdownMeta, project::ProjectMetaData};
-use yew::{platform::spawn_local, prelude::*};
+use petompp_web_models::models::blob::project::ProjectMetaData;
+use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::*;
 
@@ -31,40 +41,45 @@ pub struct ProjectProps {
 
 #[function_component(Project)]
 pub fn project(props: &ProjectProps) -> Html {
+    html! {
+        <ProjectContent
+            container={container::Project::NAME}
+            blob={BlobType::Project(props.id.clone())}
+            list={Route::Projects}/>
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct ProjectContentProps {
+    pub container: &'static str,
+    pub blob: BlobType,
+    /// Where to go when it doesn't exist.
+    pub list: Route,
+    /// Text of a link back to the list.
+    #[prop_or_default]
+    pub back: Option<TK>,
+}
+
+/// Page of a project, or of a piece of a content type shown like one.
+#[function_component(ProjectContent)]
+pub fn project_content(props: &ProjectContentProps) -> Html {
     let navigator = use_navigator().unwrap();
     let (_, session_dispatch) = use_store::<SessionStore>();
     let (locales_store, _) = use_store::<LocalesStore>();
-    let data = use_state(|| State::Ok(None));
-    use_effect_with_deps(
-        |(props, data, locales_store)| {
-            let props = props.clone();
-            let data = data.clone();
-            let locales_store = locales_store.clone();
-            match *data {
-                State::Ok(Some((_, _, last))) if last == locales_store.curr => return,
-                State::Loading | State::Err(_) => return,
-                _ => data.set(State::Loading),
-            }
-            spawn_local(async move {
-                let filename = MarkdownMeta::create_filename(props.id.as_str(), locales_store.curr);
-                match futures::join!(
-                    ApiClient::get_meta::<ProjectMetaData>("project", &filename),
-                    ApiClient::get_content_str("project", &filename),
-                ) {
-                    (Ok(meta), Ok(md)) => data.set(State::Ok(Some((meta, md, locales_store.curr)))),
-                    (Err(e), _) | (_, Err(e)) => {
-                        data.set(State::Err(e));
-                    }
-                }
-            });
+    let resid = ResId::Blob(props.blob.clone());
+    let id = resid.id().to_string();
+    let data = use_content::<ProjectMetaData>(props.container, id.clone());
+    let badge = match &data {
+        State::Ok(Some((m, _))) => html! {
+            <PublicationBadge class={"mb-4"} draft={m.is_draft()} publish_at={m.publish_at()}/>
         },
-        (props.clone(), data.clone(), locales_store.clone()),
-    );
-    let (markdown, title, gallery) = match &*data {
-        State::Ok(Some((m, md, _))) => (
+        _ => html! {},
+    };
+    let (markdown, title, gallery) = match &data {
+        State::Ok(Some((m, md))) => (
             html! {<Markdown markdown={md.clone()} allowhtml={true} interactive={Some(())}/>},
             m.title().clone(),
-            html! {<ProjectGallery id={props.id.clone()} />},
+            html! {<ProjectGallery {id} container={props.container} />},
         ),
         State::Loading | State::Ok(None) => (
             html! { <Loading /> },
@@ -72,7 +87,7 @@ pub fn project(props: &ProjectProps) -> Html {
             html! {},
         ),
         State::Err(RequestError::Endpoint(404, _)) => {
-            navigator.push(&Route::Projects);
+            navigator.push(&props.list);
             return html! {};
         }
         State::Err(e) => {
@@ -81,19 +96,25 @@ pub fn project(props: &ProjectProps) -> Html {
             }
             (
                 html! {
-                    <>
                     <h3 class={"mx-auto py-4 text-xl font-semibold text-error"}>{e.to_string()}</h3>
-                    <p>{e.to_string()}</p>
-                    </>
                 },
                 locales_store.get(TK::ErrorOccured),
                 html! {},
             )
         }
     };
+    let back = props.back.clone().map(|back| {
+        let list = props.list.clone();
+        let onclick = Callback::from(move |_| navigator.push(&list));
+        html! {
+            <a class={"lg:mb-6 mb-4"} href={"javascript:void(0);"} {onclick}>{locales_store.get(back)}</a>
+        }
+    });
     html! {
         <PageBase {title}>
-            <EditButton resid={ResId::Blob(BlobType::Project(props.id.clone()))} />
+            <EditButton {resid} />
+            {back}
+            {badge}
             <div class={"mx-auto flex flex-col w-full"}>
                 {markdown}
                 {gallery}
@@ -102,22 +123,30 @@ pub fn project(props: &ProjectProps) -> Html {
     }
 }
 
+#[derive(Clone, PartialEq, Properties)]
+pub struct ProjectGalleryProps {
+    pub id: String,
+    #[prop_or(container::Project::NAME)]
+    pub container: &'static str,
+}
+
 #[function_component(ProjectGallery)]
-pub fn project_gallery(props: &ProjectProps) -> Html {
+pub fn project_gallery(props: &ProjectGalleryProps) -> Html {
     let (_, session_dispatch) = use_store::<SessionStore>();
     let (locales_store, _) = use_store::<LocalesStore>();
     let data = use_state(|| State::Ok(None));
+    let abort = use_abort_scope();
     use_effect_with_deps(
-        |(props, data)| {
+        move |(props, data)| {
             let props = props.clone();
             let data = data.clone();
             match *data {
                 State::Ok(Some(_)) | State::Loading | State::Err(_) => return,
                 _ => data.set(State::Loading),
             }
-            spawn_local(async move {
-                let img_dir = format!("{}/images/", &props.id);
-                match ApiClient::get_names("project", Some(img_dir.as_str())).await {
+            abort.spawn(async move {
+                let img_dir = container::images_dir(&props.id);
+                match Backend::get_names(props.container, Some(img_dir.as_str())).await {
                     Ok(images) => data.set(State::Ok(Some(images))),
                     Err(RequestError::Endpoint(404, _)) => data.set(State::Ok(Some(vec![]))),
                     Err(e) => data.set(State::Err(e)),
@@ -141,16 +170,38 @@ pub fn project_gallery(props: &ProjectProps) -> Html {
                 </>
             }
         }
-        State::Ok(Some(images)) => {
-            let slides = images
+        State::Ok(Some(files)) => {
+            let (images, attachments): (Vec<_>, Vec<_>) = files
                 .iter()
+                .filter(|f| !is_thumbnail(f))
+                .partition(|f| AssetKind::from_name(f) == AssetKind::Image);
+            let slides = images
+                .into_iter()
                 .map(|i| Slide {
-                    src: <ApiClient as BlobClient>::get_url("project", i),
+                    src: container::Project::url(i),
+                    thumbnail: Some(container::Project::url(&thumbnail_path(i))),
                     ..Default::default()
                 })
                 .collect::<Vec<_>>();
+            // slide decks, demo clips and such, uploaded next to the images
+            let attachments = (!attachments.is_empty()).then(|| {
+                let items = attachments.into_iter().map(|a| {
+                    html! {<AssetPreview src={container::Project::url(a)} class={classes!("max-h-96", "mx-auto")}/>}
+                });
+                html! {
+                    <>
+                    <div class={"prose py-8"}>
+                        <h2>{locales_store.get(TK::Attachments)}</h2>
+                    </div>
+                    <div class={"flex flex-col gap-4"}>{for items}</div>
+                    </>
+                }
+            });
             html! {
+                <>
                 <Carousel {slides} />
+                {attachments}
+                </>
             }
         }
     };
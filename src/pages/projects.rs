@@ -1,23 +1,31 @@
 use crate::{
-    api::{blob::BlobClient, client::ApiClient},
+    api::{
+        backend::Backend,
+        blob::BlobClient,
+        container::{Container, Project},
+        image::thumbnail_path,
+    },
     components::{
         atoms::{
             carousel::{Carousel, Slide},
             link::RouteLink,
             loading::Loading,
             markdown::Editable,
+            publication_badge::PublicationBadge,
         },
         state::State,
     },
     data::{
         locales::{store::LocalesStore, tk::TK},
-        resources::id::ResId,
+        resources::{id::ResId, publication::Publishable},
+        session::SessionStore,
     },
+    hooks::abort::use_abort_scope,
     pages::page_base::PageBase,
     router::route::Route,
 };
 use petompp_web_models::models::blob::project::ProjectMetaData;
-use yew::{platform::spawn_local, prelude::*};
+use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::*;
 
@@ -25,10 +33,13 @@ use yewdux::prelude::*;
 pub fn projects() -> Html {
     let navigator = use_navigator().unwrap();
     let (locales_store, _) = use_store::<LocalesStore>();
+    let (session_store, _) = use_store::<SessionStore>();
+    let is_admin = session_store.is_admin();
     let state = use_state(|| State::Ok(None));
     let prev = use_state(|| locales_store.curr);
+    let abort = use_abort_scope();
     use_effect_with_deps(
-        |(state, curr, prev)| {
+        move |(state, curr, prev)| {
             match &**state {
                 State::Ok(Some(_)) | State::Loading | State::Err(_) if *curr == **prev => return,
                 _ => state.set(State::Loading),
@@ -36,8 +47,8 @@ pub fn projects() -> Html {
             let state = state.clone();
             let curr = *curr;
             prev.set(curr);
-            spawn_local(async move {
-                match ApiClient::get_meta_all::<ProjectMetaData>("project", None).await {
+            abort.spawn(async move {
+                match Backend::get_meta_all::<ProjectMetaData>(Project::NAME, None).await {
                     Ok(m) => state.set(State::Ok(Some(
                         m.into_iter()
                             .filter(|m| m.lang() == curr)
@@ -52,34 +63,44 @@ pub fn projects() -> Html {
 
     // TODO: Top 3 most viewed + most recent
     let (slides, projects) = match &*state {
-        State::Ok(Some(m)) => (
-            m.iter()
-                .map(|p| {
-                    let id = p.id().to_string();
-                    let src = <ApiClient as BlobClient>::get_url(
-                        "project",
-                        format!("{}/images/{}", &id, p.splash().cloned().unwrap_or_default())
-                            .as_str(),
-                    );
-                    let onclick = {
-                        let id = id.clone();
-                        let navigator = navigator.clone();
-                        Some(Callback::from(move |_| {
+        State::Ok(Some(m)) => {
+            let m = m
+                .iter()
+                .filter(|p| is_admin || p.is_public())
+                .cloned()
+                .collect::<Vec<_>>();
+            (
+                m.iter()
+                    .map(|p| {
+                        let id = p.id().to_string();
+                        let splash = format!(
+                            "{}{}",
+                            Project::images_dir(&id),
+                            p.splash().cloned().unwrap_or_default()
+                        );
+                        let src = Project::url(&splash);
+                        let thumbnail = Some(Project::url(&thumbnail_path(&splash)));
+                        let onclick = {
                             let id = id.clone();
                             let navigator = navigator.clone();
-                            navigator.push(&Route::Project { id })
-                        }))
-                    };
-                    Slide {
-                        src,
-                        title: p.title().clone(),
-                        summary: Some(p.summary().clone()),
-                        onclick,
-                    }
-                })
-                .collect::<Vec<_>>(),
-            m.clone(),
-        ),
+                            Some(Callback::from(move |_| {
+                                let id = id.clone();
+                                let navigator = navigator.clone();
+                                navigator.push(&Route::Project { id })
+                            }))
+                        };
+                        Slide {
+                            src,
+                            thumbnail,
+                            title: p.title().clone(),
+                            summary: Some(p.summary().clone()),
+                            onclick,
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+                m,
+            )
+        }
         State::Err(e) => {
             return html! {<p class={"text-xl text-error font-semibold"}>{e.to_string()}</p>}
         }
@@ -90,6 +111,7 @@ pub fn projects() -> Html {
         html! {
             <li>
                 <RouteLink route={Route::Project { id }} text={p.title().clone()}/>
+                <PublicationBadge class={"badge-sm not-prose ml-2"} draft={p.is_draft()} publish_at={p.publish_at()}/>
             </li>
         }
     });
@@ -1,4 +1,4 @@
-use crate::api::client::ApiClient;
+use crate::api::backend::Backend;
 use crate::api::settings::SettingsClient;
 use crate::api::user::UserClient;
 use crate::components::atoms::text_input::{InputType, TextInput};
@@ -33,7 +33,7 @@ pub fn register() -> Html {
             }
             requirements.set(State::Loading);
             spawn_local(async move {
-                match ApiClient::get_user_settings().await {
+                match Backend::get_user_settings().await {
                     Ok(req) => requirements.set(State::Ok(Some(req))),
                     Err(error) => requirements.set(State::Err(error)),
                 }
@@ -63,7 +63,7 @@ pub fn register() -> Html {
     let onsubmit = async_event!(
     [prevent SubmitEvent] |form_data, history, locales_store, requirements| {
             let creds = (*form_data).clone();
-            match ApiClient::register(creds).await {
+            match Backend::register(creds).await {
             Ok(()) => {
                 history.push(&Route::Login);
             },
@@ -88,6 +88,9 @@ pub fn register() -> Html {
                     RequestError::Parse(error) | RequestError::Network(error) => {
                         show_error(error, Some((&Route::Home, &history)))
                     }
+                    RequestError::Timeout => {
+                        show_error(locales_store.get(TK::RequestTimedOut), Some((&Route::Home, &history)))
+                    }
                 }
             }
         }
@@ -1,5 +1,5 @@
 use crate::pages::{
-    admin::{admin_panel::AdminPanel, user_management::UserManagement},
+    admin::{admin_panel::AdminPanel, drafts::Drafts, user_management::UserManagement},
     not_found::NotFound,
 };
 use yew::prelude::*;
@@ -11,6 +11,8 @@ pub enum AdminRoute {
     AdminPanel,
     #[at("/admin/user_management")]
     UserManagement,
+    #[at("/admin/drafts")]
+    Drafts,
     #[not_found]
     #[at("/admin/404")]
     NotFound,
@@ -21,6 +23,7 @@ impl AdminRoute {
         match route {
             AdminRoute::AdminPanel => html! { <AdminPanel />},
             AdminRoute::UserManagement => html! { <UserManagement />},
+            AdminRoute::Drafts => html! { <Drafts />},
             AdminRoute::NotFound => html! {<NotFound />},
         }
     }
@@ -1,7 +1,17 @@
 use crate::{
+    data::{config::ConfigStore, resources::id::BlobType},
     pages::{
-        about::About, blog::Blog, blog_post::BlogPost, contact::Contact, editor::Editor,
-        home::Home, login::Login, not_found::NotFound, project::Project, projects::Projects,
+        about::About,
+        blog::Blog,
+        blog_post::BlogPost,
+        contact::Contact,
+        content::{ContentItem, ContentList},
+        editor::Editor,
+        home::Home,
+        login::Login,
+        not_found::NotFound,
+        project::Project,
+        projects::Projects,
         register::Register,
     },
     router::admin::AdminRoute,
@@ -36,6 +46,10 @@ pub enum Route {
     Projects,
     #[at("/project/:id")]
     Project { id: String },
+    #[at("/content/:kind")]
+    ContentList { kind: String },
+    #[at("/content/:kind/:id")]
+    ContentItem { kind: String, id: String },
     // Admin routes
     #[at("/admin")]
     AdminRoot,
@@ -52,6 +66,19 @@ impl Display for Route {
     }
 }
 
+impl From<&BlobType> for Route {
+    fn from(value: &BlobType) -> Self {
+        match value {
+            BlobType::Blog(id) => Route::BlogPost { id: id.clone() },
+            BlobType::Project(id) => Route::Project { id: id.clone() },
+            BlobType::Content(kind, id) => Route::ContentItem {
+                kind: kind.clone(),
+                id: id.clone(),
+            },
+        }
+    }
+}
+
 impl Route {
     pub fn switch(self) -> Html {
         match self {
@@ -60,15 +87,17 @@ impl Route {
             Route::BlogPost { id } => html! {<BlogPost {id} />},
             Route::Projects => html! {<Projects />},
             Route::Project { id } => html! {<Project {id} />},
+            Route::ContentList { kind } => html! {<ContentList {kind} />},
+            Route::ContentItem { kind, id } => html! {<ContentItem {kind} {id} />},
             Route::About => html! {<About />},
             Route::Contact => html! {<Contact />},
             Route::Login => html! {<Login />},
             Route::Register => html! {<Register />},
-            Route::Editor => html! { <Editor />},
+            Route::Editor if ConfigStore::get().features.editor => html! { <Editor />},
             Route::AdminRoot | Route::Admin => {
                 html! {<Switch<AdminRoute> render={AdminRoute::switch} />}
             }
-            Route::NotFound => html! {  <NotFound />},
+            Route::Editor | Route::NotFound => html! {  <NotFound />},
         }
     }
 
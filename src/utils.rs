@@ -62,9 +62,361 @@ pub mod style {
     }
 }
 
+pub mod date {
+    use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+
+    const INPUT_FORMAT: &str = "%Y-%m-%dT%H:%M";
+    /// Browsers add the seconds, and their fraction, when the input has a `step`
+    /// under a minute or the value set has them.
+    const INPUT_FORMAT_SECONDS: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+    /// Value of a `datetime-local` input, in the local time zone.
+    pub fn to_input_value(date: DateTime<Utc>) -> String {
+        DateTime::<Local>::from(date)
+            .format(INPUT_FORMAT)
+            .to_string()
+    }
+
+    pub fn from_input_value(value: &str) -> Option<DateTime<Utc>> {
+        let date = NaiveDateTime::parse_from_str(value, INPUT_FORMAT)
+            .or_else(|_| NaiveDateTime::parse_from_str(value, INPUT_FORMAT_SECONDS))
+            .ok()?;
+        Local
+            .from_local_datetime(&date)
+            .earliest()
+            .map(|d| d.with_timezone(&Utc))
+    }
+}
+
+pub mod diff {
+    /// Longer edits are shown as a whole replacement, to keep the memory bounded.
+    const MAX_EDITS: usize = 2000;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Change<'a> {
+        Same(&'a str),
+        Added(&'a str),
+        Removed(&'a str),
+    }
+
+    pub fn lines<'a>(old: &'a str, new: &'a str) -> Vec<Change<'a>> {
+        let old = old.lines().collect::<Vec<_>>();
+        let new = new.lines().collect::<Vec<_>>();
+        diff(&old, &new)
+    }
+
+    /// Words with the whitespace following them.
+    pub fn words<'a>(old: &'a str, new: &'a str) -> Vec<Change<'a>> {
+        let old = old.split_inclusive(char::is_whitespace).collect::<Vec<_>>();
+        let new = new.split_inclusive(char::is_whitespace).collect::<Vec<_>>();
+        diff(&old, &new)
+    }
+
+    pub fn diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Change<'a>> {
+        let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let mut changes = old[..prefix]
+            .iter()
+            .map(|s| Change::Same(s))
+            .collect::<Vec<_>>();
+        changes.extend(myers(
+            &old[prefix..old.len() - suffix],
+            &new[prefix..new.len() - suffix],
+        ));
+        changes.extend(old[old.len() - suffix..].iter().map(|s| Change::Same(s)));
+        changes
+    }
+
+    /// Two edits of the same text combined, line by line.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Merge {
+        pub text: String,
+        /// Places changed differently on both sides, left between conflict markers.
+        pub conflicts: usize,
+    }
+
+    /// Lines changed on one side only are taken from it. Changes that touch
+    /// or overlap are conflicts unless both sides made the same one.
+    pub fn merge(base: &str, mine: &str, theirs: &str) -> Merge {
+        let base_lines = base.lines().collect::<Vec<_>>();
+        let mut mine_hunks = hunks(&lines(base, mine)).into_iter().peekable();
+        let mut theirs_hunks = hunks(&lines(base, theirs)).into_iter().peekable();
+        let mut out = Vec::new();
+        let (mut pos, mut conflicts) = (0, 0);
+        loop {
+            let start = match (mine_hunks.peek(), theirs_hunks.peek()) {
+                (Some(m), Some(t)) => m.start.min(t.start),
+                (Some(h), None) | (None, Some(h)) => h.start,
+                (None, None) => break,
+            };
+            let (mut group_mine, mut group_theirs) = (Vec::new(), Vec::new());
+            let mut end = start;
+            loop {
+                if let Some(h) = mine_hunks.next_if(|h| h.start <= end) {
+                    end = end.max(h.end);
+                    group_mine.push(h);
+                } else if let Some(h) = theirs_hunks.next_if(|h| h.start <= end) {
+                    end = end.max(h.end);
+                    group_theirs.push(h);
+                } else {
+                    break;
+                }
+            }
+            out.extend(&base_lines[pos..start]);
+            let mine_side = apply(&base_lines, start, end, &group_mine);
+            let theirs_side = apply(&base_lines, start, end, &group_theirs);
+            if group_theirs.is_empty() || mine_side == theirs_side {
+                out.extend(mine_side);
+            } else if group_mine.is_empty() {
+                out.extend(theirs_side);
+            } else {
+                conflicts += 1;
+                out.push("<<<<<<< draft");
+                out.extend(mine_side);
+                out.push("=======");
+                out.extend(theirs_side);
+                out.push(">>>>>>> saved");
+            }
+            pos = end;
+        }
+        out.extend(&base_lines[pos..]);
+        let mut text = out.join("\n");
+        if mine.ends_with('\n') && !text.is_empty() {
+            text.push('\n');
+        }
+        Merge { text, conflicts }
+    }
+
+    /// Lines `start..end` of the base replaced by `lines`.
+    struct Hunk<'a> {
+        start: usize,
+        end: usize,
+        lines: Vec<&'a str>,
+    }
+
+    fn hunks<'a>(changes: &[Change<'a>]) -> Vec<Hunk<'a>> {
+        let mut hunks = Vec::new();
+        let mut current = None::<Hunk>;
+        let mut pos = 0;
+        for change in changes {
+            match change {
+                Change::Same(_) => {
+                    hunks.extend(current.take());
+                    pos += 1;
+                }
+                Change::Removed(_) => {
+                    pos += 1;
+                    current
+                        .get_or_insert(Hunk {
+                            start: pos - 1,
+                            end: pos - 1,
+                            lines: Vec::new(),
+                        })
+                        .end = pos;
+                }
+                Change::Added(s) => current
+                    .get_or_insert(Hunk {
+                        start: pos,
+                        end: pos,
+                        lines: Vec::new(),
+                    })
+                    .lines
+                    .push(s),
+            }
+        }
+        hunks.extend(current);
+        hunks
+    }
+
+    /// One side's version of the base lines `start..end`.
+    fn apply<'a>(base: &[&'a str], start: usize, end: usize, hunks: &[Hunk<'a>]) -> Vec<&'a str> {
+        let mut out = Vec::new();
+        let mut pos = start;
+        for hunk in hunks {
+            out.extend(&base[pos..hunk.start]);
+            out.extend(&hunk.lines);
+            pos = hunk.end;
+        }
+        out.extend(&base[pos..end]);
+        out
+    }
+
+    /// Shortest edit script, as described by Eugene W. Myers.
+    fn myers<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<Change<'a>> {
+        let (n, m) = (a.len() as isize, b.len() as isize);
+        let max = a.len() + b.len();
+        let offset = max as isize + 1;
+        let mut v = vec![0isize; 2 * max + 3];
+        // Diagonals around the searched ones, before each round
+        let mut trace = Vec::new();
+        'search: for d in 0..=max.min(MAX_EDITS) as isize {
+            let lo = (offset - d - 1) as usize;
+            trace.push(v[lo..lo + 2 * d as usize + 3].to_vec());
+            for k in (-d..=d).step_by(2) {
+                let i = (offset + k) as usize;
+                let mut x = match k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                    true => v[i + 1],
+                    false => v[i - 1] + 1,
+                };
+                let mut y = x - k;
+                while x < n && y < m && a[x as usize] == b[y as usize] {
+                    x += 1;
+                    y += 1;
+                }
+                v[i] = x;
+                if x >= n && y >= m {
+                    break 'search;
+                }
+            }
+            if d as usize == MAX_EDITS {
+                return a
+                    .iter()
+                    .map(|s| Change::Removed(s))
+                    .chain(b.iter().map(|s| Change::Added(s)))
+                    .collect();
+            }
+        }
+        let mut changes = Vec::new();
+        let (mut x, mut y) = (n, m);
+        for (d, v) in trace.iter().enumerate().rev() {
+            let d = d as isize;
+            let at = |k: isize| v[(k + d + 1) as usize];
+            let k = x - y;
+            let prev_k = match k == -d || (k != d && at(k - 1) < at(k + 1)) {
+                true => k + 1,
+                false => k - 1,
+            };
+            let prev_x = at(prev_k);
+            let prev_y = prev_x - prev_k;
+            while x > prev_x && y > prev_y {
+                changes.push(Change::Same(a[x as usize - 1]));
+                x -= 1;
+                y -= 1;
+            }
+            if d > 0 {
+                match x == prev_x {
+                    true => changes.push(Change::Added(b[y as usize - 1])),
+                    false => changes.push(Change::Removed(a[x as usize - 1])),
+                }
+                (x, y) = (prev_x, prev_y);
+            }
+        }
+        changes.reverse();
+        changes
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn diff_finds_inserted_lines() {
+            assert_eq!(
+                lines("a\nb\nc", "a\nb\nx\nc"),
+                [
+                    Change::Same("a"),
+                    Change::Same("b"),
+                    Change::Added("x"),
+                    Change::Same("c"),
+                ]
+            );
+            assert_eq!(lines("", "a"), [Change::Added("a")]);
+        }
+
+        #[test]
+        fn diff_finds_removed_lines() {
+            assert_eq!(
+                lines("a\nb\nc", "a\nc"),
+                [Change::Same("a"), Change::Removed("b"), Change::Same("c")]
+            );
+        }
+
+        #[test]
+        fn diff_replaces_changed_lines() {
+            assert_eq!(
+                lines("a\nb\nc", "a\nx\nc"),
+                [
+                    Change::Same("a"),
+                    Change::Removed("b"),
+                    Change::Added("x"),
+                    Change::Same("c"),
+                ]
+            );
+        }
+
+        #[test]
+        fn diff_is_the_shortest() {
+            let (old, new) = ("a\nb\nc\na\nb\nb\na", "c\nb\na\nb\na\nc");
+            let changes = lines(old, new);
+            let edits = changes
+                .iter()
+                .filter(|c| !matches!(c, Change::Same(_)))
+                .count();
+            assert_eq!(edits, 5);
+            let kept = |added: bool| {
+                changes
+                    .iter()
+                    .filter_map(|c| match (c, added) {
+                        (Change::Same(s), _)
+                        | (Change::Added(s), true)
+                        | (Change::Removed(s), false) => Some(*s),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            assert_eq!(kept(false), old);
+            assert_eq!(kept(true), new);
+        }
+
+        #[test]
+        fn merge_takes_edits_of_different_lines_from_both_sides() {
+            let merged = merge("a\nb\nc\nd\ne", "a\nB\nc\nd\ne", "a\nb\nc\nD\ne");
+            assert_eq!(merged.text, "a\nB\nc\nD\ne");
+            assert_eq!(merged.conflicts, 0);
+        }
+
+        #[test]
+        fn merge_takes_an_insert_and_a_delete() {
+            let merged = merge("a\nb\nc", "a\nx\nb\nc", "a\nb");
+            assert_eq!(merged.text, "a\nx\nb");
+            assert_eq!(merged.conflicts, 0);
+        }
+
+        #[test]
+        fn merge_marks_overlapping_edits() {
+            let merged = merge("a\nb\nc", "a\nx\nc", "a\ny\nc");
+            assert_eq!(
+                merged.text,
+                "a\n<<<<<<< draft\nx\n=======\ny\n>>>>>>> saved\nc"
+            );
+            assert_eq!(merged.conflicts, 1);
+        }
+
+        #[test]
+        fn merge_takes_the_same_edit_once() {
+            let merged = merge("a\nb\nc", "a\nx\nc", "a\nx\nc");
+            assert_eq!(merged.text, "a\nx\nc");
+            assert_eq!(merged.conflicts, 0);
+        }
+
+        #[test]
+        fn merge_keeps_the_trailing_newline() {
+            let merged = merge("a\n", "a\nb\n", "a\n");
+            assert_eq!(merged.text, "a\nb\n");
+        }
+    }
+}
+
 pub mod js {
     use wasm_bindgen::JsCast;
-    use web_sys::{Element, HtmlInputElement};
+    use web_sys::{Element, HtmlElement, HtmlImageElement, HtmlInputElement};
+    use yew::{Callback, Event, TargetCast};
 
     pub fn get_textarea(textarea_id: &str) -> HtmlInputElement {
         web_sys::window()
@@ -122,4 +474,35 @@ pub mod js {
                 .unwrap_or_default() as usize,
         )
     }
+
+    /// Saves `content` as a file named `name` through a temporary link.
+    pub fn download(name: &str, content: &str, mime: &str) {
+        let blob = gloo::file::Blob::new_with_options(content, Some(mime));
+        let url = gloo::file::ObjectUrl::from(blob);
+        let Some(link) = web_sys::window()
+            .and_then(|w| w.document())
+            .and_then(|d| d.create_element("a").ok())
+            .and_then(|e| e.dyn_into::<HtmlElement>().ok())
+        else {
+            gloo::console::error!(format!("failed to download {}", name));
+            return;
+        };
+        let _ = link.set_attribute("href", &url);
+        let _ = link.set_attribute("download", name);
+        link.click();
+        // the download may start after the click returns, so the url is revoked later
+        gloo::timers::callback::Timeout::new(60_000, move || drop(url)).forget();
+    }
+
+    /// `onerror` handler of an `img` that switches to `src` once,
+    /// used for thumbnails that may not exist yet.
+    pub fn fallback_src(src: String) -> Callback<Event> {
+        Callback::from(move |e: Event| {
+            let img = e.target_unchecked_into::<HtmlImageElement>();
+            if img.get_attribute("data-fallback").as_ref() != Some(&src) {
+                img.set_attribute("data-fallback", &src).unwrap();
+                img.set_src(&src);
+            }
+        })
+    }
 }